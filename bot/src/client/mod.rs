@@ -1,8 +1,11 @@
 //! HTTP client module for wrapper service communication.
 
+mod cluster;
 mod wrapper;
 
+pub use cluster::{ClusterClient, ClusterNode, NodeStatus};
 pub use wrapper::{
-    ApprovalSubmission, ExecutionMode, ProjectRequest, RegisterLocalRequest,
-    TaskRequest, TaskStatus, WrapperClient,
+    ApprovalOption, ApprovalRequest, ApprovalSubmission, ExecutionMode, ProjectRequest,
+    RegisterLocalRequest, RetryPolicy, TaskEvent, TaskEventMessage, TaskRequest, TaskResponse,
+    TaskStatus, WrapperClient,
 };
@@ -1,8 +1,40 @@
 //! HTTP client for communicating with the Claude wrapper service.
 
-use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::{anyhow, Context, Result};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use argon2::password_hash::SaltString;
+use argon2::{Argon2, PasswordHasher};
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+use reqwest::header::HeaderMap;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Hash a raw auth token with argon2id (PHC string format) so registration
+/// never sends the raw secret - only this hash is sent to the wrapper
+/// service for it to store and later compare against. The raw token itself
+/// still travels as the `Authorization: Bearer` header on task dispatches.
+fn hash_auth_token(token: &str) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(token.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| anyhow!("Failed to hash auth token: {}", e))
+}
+
+/// Generate a new random auth token (32 random bytes, hex-encoded).
+fn generate_auth_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
 
 /// Task status enum matching the wrapper service.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -54,6 +86,14 @@ pub struct TaskRequest {
     pub target_user_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mode: Option<ExecutionMode>,
+    /// Per-request bearer token override: set when delegating to another
+    /// user's wrapper via `target_user_id`, or when the submitting user
+    /// registered their own auth token (see `WrapperClient::register_local`)
+    /// and it needs to ride along on their own dispatch. Never serialized
+    /// into the JSON body — it's consumed client-side to set the
+    /// `Authorization` header.
+    #[serde(skip)]
+    pub delegated_token: Option<String>,
 }
 
 /// Request to add a new project.
@@ -73,10 +113,46 @@ pub struct ProjectResponse {
     pub path: String,
     pub description: String,
     pub owner_id: String,
+    /// Discord user IDs (other than the owner) allowed to run `/task`
+    /// against this project.
+    #[serde(default)]
+    pub authorized_ids: Vec<String>,
     pub created_at: String,
 }
 
-/// Request to register a local wrapper.
+/// A single entry in a project's activity history (e.g. added, removed,
+/// shared, or a `/task` run against it).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectHistoryEntry {
+    pub action: String,
+    pub actor_id: String,
+    #[serde(default)]
+    pub detail: Option<String>,
+    pub timestamp: String,
+}
+
+/// Newest-first page of a project's activity history.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectHistoryResponse {
+    pub entries: Vec<ProjectHistoryEntry>,
+}
+
+/// Request to share a project with another user.
+#[derive(Debug, Serialize)]
+pub struct ShareProjectRequest {
+    pub target_user_id: String,
+}
+
+/// Request to transfer a project's ownership to another user.
+#[derive(Debug, Serialize)]
+pub struct TransferProjectRequest {
+    pub new_owner_id: String,
+}
+
+/// Request to register a local wrapper. `auth_token` is the *raw* token as
+/// typed by the user - `WrapperClient::register_local` hashes it before it
+/// ever reaches the wire. `capabilities` is filled in by `register_local`
+/// itself from the reachability probe, not by the caller.
 #[derive(Debug, Serialize)]
 pub struct RegisterLocalRequest {
     pub discord_id: String,
@@ -84,6 +160,111 @@ pub struct RegisterLocalRequest {
     pub wrapper_url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub auth_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<WrapperCapabilities>,
+}
+
+/// What a wrapper declares about itself when probed at registration time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrapperCapabilities {
+    pub runtimes: Vec<String>,
+    pub max_concurrency: u32,
+    pub resource_path: String,
+}
+
+/// How long the reachability probe in `register_local` waits for a response
+/// before giving up.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How many redirect hops the probe follows before giving up with "too many
+/// redirects", matching the pattern of a browser bailing on a redirect loop.
+const MAX_REDIRECTS: u32 = 10;
+
+/// Probe a candidate wrapper URL before trusting it: follow redirects
+/// ourselves (instead of `reqwest`'s default policy) so we can cap the hop
+/// count and reject loops or relative `Location` targets, then parse the
+/// declared capabilities from the final response.
+async fn probe_wrapper_url(url: &str) -> Result<WrapperCapabilities> {
+    let client = Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(PROBE_TIMEOUT)
+        .build()
+        .context("Failed to build probe client")?;
+
+    let mut current = url.to_string();
+    let mut visited = HashSet::new();
+
+    for _ in 0..=MAX_REDIRECTS {
+        if !visited.insert(current.clone()) {
+            return Err(anyhow!("redirect loop detected"));
+        }
+
+        let response = client.get(&current).send().await.map_err(|e| {
+            if e.is_timeout() {
+                anyhow!("timed out after {}s", PROBE_TIMEOUT.as_secs())
+            } else if e.is_connect() {
+                anyhow!("connection refused")
+            } else {
+                anyhow!("probe request failed: {}", e)
+            }
+        })?;
+
+        if response.status().is_redirection() {
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| anyhow!("redirect with no Location header"))?;
+
+            let next = reqwest::Url::parse(location)
+                .map_err(|_| anyhow!("redirect target '{}' is not an absolute URL", location))?;
+            current = next.to_string();
+            continue;
+        }
+
+        if !response.status().is_success() {
+            return Err(anyhow!("wrapper responded with status {}", response.status()));
+        }
+
+        return response.json().await.context("Failed to parse wrapper capabilities");
+    }
+
+    Err(anyhow!("too many redirects (limit {})", MAX_REDIRECTS))
+}
+
+/// Request to replace a user's stored auth token hash.
+#[derive(Debug, Serialize)]
+struct RotateTokenRequest {
+    auth_token_hash: String,
+}
+
+/// One node in the cluster, as returned by `WrapperClient::list_nodes`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodeInfo {
+    pub node_id: String,
+    pub address: String,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    pub owning_discord_id: String,
+    pub healthy: bool,
+}
+
+/// Response body of `GET /api/v1/cluster/nodes`.
+#[derive(Debug, Deserialize)]
+struct NodeListResponse {
+    nodes: Vec<NodeInfo>,
+}
+
+/// Response body of `GET /api/v1/cluster/allocate`.
+#[derive(Debug, Deserialize)]
+struct AllocateNodeResponse {
+    node_id: String,
+}
+
+/// Request to pin a user's cluster-mode tasks to a specific node.
+#[derive(Debug, Serialize)]
+struct SetClusterNodeRequest {
+    node_id: String,
 }
 
 /// Request to enable cluster access.
@@ -112,6 +293,20 @@ pub struct UserResponse {
     pub default_mode: String,
     pub created_at: String,
     pub last_seen: String,
+    /// Whether the wrapper requires an `Authorization: Bearer` token for
+    /// this user (set via `/register local token:` or `/register
+    /// rotate-token`). Never the token itself - just whether one exists.
+    #[serde(default)]
+    pub auth_token_set: bool,
+    /// What the wrapper declared about itself when `register_local` probed
+    /// it. Absent for wrappers registered before this field existed.
+    #[serde(default)]
+    pub capabilities: Option<WrapperCapabilities>,
+    /// The cluster node this user's tasks are pinned to, if they've run
+    /// `/register cluster node:<id>`. `None` means the orchestrator's
+    /// default allocation (see `WrapperClient::allocate_node`) picks one.
+    #[serde(default)]
+    pub cluster_node_id: Option<String>,
 }
 
 /// Approval option from the wrapper service.
@@ -151,6 +346,36 @@ pub struct ApprovalSubmission {
     pub custom_response: Option<String>,
 }
 
+/// An event on a task's live stream, tagged by `kind` on the wire. Unknown
+/// `kind` values decode to `Unknown` instead of failing, so the bot doesn't
+/// break when the wrapper service adds a new event type.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind")]
+pub enum TaskEvent {
+    OutputChunk { text: String },
+    StatusChanged { status: TaskStatus },
+    ApprovalNeeded { request: ApprovalRequest },
+    Done { response: TaskResponse },
+    #[serde(other)]
+    Unknown,
+}
+
+/// One task-lifecycle event delivered over the wrapper's durable
+/// "task-events" queue: a status transition plus who to notify about it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaskEventMessage {
+    pub event_id: String,
+    pub task_id: String,
+    pub status: TaskStatus,
+    pub discord_user_id: String,
+}
+
+/// A batch of queued task events returned by one long-poll round-trip.
+#[derive(Debug, Deserialize)]
+struct TaskEventBatch {
+    events: Vec<TaskEventMessage>,
+}
+
 /// Session information.
 #[derive(Debug, Deserialize)]
 pub struct SessionInfo {
@@ -179,6 +404,12 @@ pub struct ShareRequest {
     pub target_user_id: String,
 }
 
+/// Request to transfer wrapper ownership to another user.
+#[derive(Debug, Serialize)]
+pub struct TransferOwnershipRequest {
+    pub new_owner_id: String,
+}
+
 /// Response listing shared users.
 #[derive(Debug, Deserialize)]
 pub struct ShareListResponse {
@@ -199,19 +430,203 @@ pub struct AccessibleWrappersResponse {
     pub wrappers: Vec<AccessibleWrapper>,
 }
 
+/// One page of a cursor-paginated collection.
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    items: Vec<T>,
+    next_cursor: Option<String>,
+}
+
+/// Number of items requested per cursor round-trip by the `*_stream` methods.
+const PAGE_LIMIT: u32 = 50;
+
+/// State driving a `paginate` stream: the client to fetch with, the
+/// cursor-less base URL, the last cursor seen, a buffer of not-yet-yielded
+/// items from the current page, and whether the server has reported no
+/// further pages.
+struct PageState<T> {
+    client: WrapperClient,
+    base_url: String,
+    cursor: Option<String>,
+    buffer: std::collections::VecDeque<T>,
+    done: bool,
+}
+
+/// Controls retries for idempotent (GET/DELETE) requests: how many times to
+/// retry a transient failure, and the exponential backoff between attempts.
+/// Writes are always sent once and never consult this policy.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff for the given (zero-indexed) attempt: `base_delay * 2^attempt`
+    /// capped at `max_delay`, plus up to 50% jitter so retrying clients don't
+    /// all wake up at the same instant.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self.base_delay.saturating_mul(1u32 << attempt.min(16));
+        let capped = exponential.min(self.max_delay);
+        let jitter_ms = (capped.as_millis() as u64 / 2).max(1);
+        capped + Duration::from_millis(rand::random::<u64>() % jitter_ms)
+    }
+}
+
+/// Tracks the wrapper service's last-reported rate limit window, shared
+/// across clones of a `WrapperClient` so every GET/DELETE sent through
+/// `send_idempotent` backs off together instead of racing to exhaust the
+/// same quota. POST requests (task submissions included) neither wait on
+/// nor update it.
+#[derive(Debug)]
+struct RateLimitState {
+    /// Remaining requests in the current window; `u32::MAX` means "unknown"
+    /// (no `X-RateLimit-*` headers seen yet, so don't throttle).
+    remaining: AtomicU32,
+    reset_at: Mutex<Option<Instant>>,
+}
+
+impl Default for RateLimitState {
+    fn default() -> Self {
+        Self {
+            remaining: AtomicU32::new(u32::MAX),
+            reset_at: Mutex::new(None),
+        }
+    }
+}
+
 /// HTTP client for the wrapper service.
 #[derive(Debug, Clone)]
 pub struct WrapperClient {
     client: Client,
     base_url: String,
+    retry_policy: RetryPolicy,
+    rate_limit: Arc<RateLimitState>,
+    /// Raw bearer token, kept alongside the reqwest client's default headers
+    /// (which only `reqwest::Client` itself reads) so `stream_task` can also
+    /// attach it to a `tokio_tungstenite` handshake request.
+    auth_token: Option<String>,
 }
 
 impl WrapperClient {
-    /// Create a new wrapper client.
+    /// Create a new wrapper client with no authentication.
     pub fn new(base_url: &str) -> Self {
         Self {
             client: Client::new(),
             base_url: base_url.trim_end_matches('/').to_string(),
+            retry_policy: RetryPolicy::default(),
+            rate_limit: Arc::new(RateLimitState::default()),
+            auth_token: None,
+        }
+    }
+
+    /// Create a wrapper client that sends `Authorization: Bearer <token>` on
+    /// every request by default. Individual requests can still override the
+    /// bearer token (see `TaskRequest::delegated_token`) for collaborative
+    /// access to someone else's wrapper.
+    pub fn with_auth(base_url: &str, token: &str) -> Self {
+        let mut headers = HeaderMap::new();
+        if let Ok(value) = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token)) {
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+        }
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            retry_policy: RetryPolicy::default(),
+            rate_limit: Arc::new(RateLimitState::default()),
+            auth_token: Some(token.to_string()),
+        }
+    }
+
+    /// Override the retry policy used for idempotent requests.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Send a GET or DELETE request, retrying transient failures per
+    /// `retry_policy` and proactively waiting out an exhausted rate limit
+    /// window beforehand. Retries both transport-level failures (timeouts,
+    /// connection resets - a `?` on `.send()` would otherwise propagate
+    /// these immediately) and 429/502/503 responses. Only safe for
+    /// idempotent requests.
+    async fn send_idempotent(&self, method: reqwest::Method, url: &str) -> reqwest::Result<reqwest::Response> {
+        self.wait_for_rate_limit().await;
+
+        let mut attempt = 0;
+        loop {
+            let outcome = self.client.request(method.clone(), url).send().await;
+
+            let response = match outcome {
+                Ok(response) => response,
+                Err(e) if (e.is_timeout() || e.is_connect()) && attempt < self.retry_policy.max_retries => {
+                    let delay = self.retry_policy.backoff(attempt);
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            self.record_rate_limit(response.headers());
+
+            let status = response.status();
+            let retryable = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                || status == reqwest::StatusCode::BAD_GATEWAY
+                || status == reqwest::StatusCode::SERVICE_UNAVAILABLE;
+
+            if retryable && attempt < self.retry_policy.max_retries {
+                let delay = retry_after_delay(response.headers())
+                    .unwrap_or_else(|| self.retry_policy.backoff(attempt));
+                attempt += 1;
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+
+            return Ok(response);
+        }
+    }
+
+    /// If the last response reported a zero remaining quota, sleep until
+    /// its reset time before sending the next request.
+    async fn wait_for_rate_limit(&self) {
+        if self.rate_limit.remaining.load(Ordering::Relaxed) != 0 {
+            return;
+        }
+
+        let reset_at = *self.rate_limit.reset_at.lock().unwrap();
+        if let Some(reset_at) = reset_at {
+            if let Some(wait) = reset_at.checked_duration_since(Instant::now()) {
+                tokio::time::sleep(wait).await;
+            }
+        }
+    }
+
+    /// Record `X-RateLimit-Remaining`/`X-RateLimit-Reset` from a response,
+    /// if present.
+    fn record_rate_limit(&self, headers: &HeaderMap) {
+        if let Some(remaining) = header_u32(headers, "x-ratelimit-remaining") {
+            self.rate_limit.remaining.store(remaining, Ordering::Relaxed);
+        }
+        if let Some(reset_secs) = header_u32(headers, "x-ratelimit-reset") {
+            let reset_at = Instant::now() + Duration::from_secs(reset_secs as u64);
+            *self.rate_limit.reset_at.lock().unwrap() = Some(reset_at);
         }
     }
 
@@ -219,9 +634,7 @@ impl WrapperClient {
     pub async fn health_check(&self) -> Result<HealthResponse> {
         let url = format!("{}/api/v1/health", self.base_url);
         let response = self
-            .client
-            .get(&url)
-            .send()
+            .send_idempotent(reqwest::Method::GET, &url)
             .await
             .context("Failed to connect to wrapper service")?;
 
@@ -231,21 +644,25 @@ impl WrapperClient {
             .context("Failed to parse health response")
     }
 
-    /// Submit a new task to the wrapper service.
+    /// Submit a new task to the wrapper service. If `request.delegated_token`
+    /// is set (e.g. the caller is running against a shared `target_user_id`
+    /// wrapper), it overrides this client's default bearer token for just
+    /// this request.
     pub async fn submit_task(&self, request: TaskRequest) -> Result<TaskResponse> {
         let url = format!("{}/api/v1/tasks", self.base_url);
-        let response = self
-            .client
-            .post(&url)
+        let mut builder = self.client.post(&url);
+        if let Some(token) = &request.delegated_token {
+            builder = builder.bearer_auth(token);
+        }
+
+        let response = builder
             .json(&request)
             .send()
             .await
             .context("Failed to submit task")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Task submission failed ({}): {}", status, body);
+            return Err(status_error("submit task", response).await);
         }
 
         response
@@ -263,23 +680,201 @@ impl WrapperClient {
             "{}/api/v1/tasks/{}?discord_user_id={}",
             self.base_url, task_id, user_id
         );
+        let response = self
+            .send_idempotent(reqwest::Method::GET, &url)
+            .await
+            .context("Failed to get task")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("get task", response).await);
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse task response")
+    }
+
+    /// Cancel a `Running` or `Pending` task, returning its resulting status
+    /// (typically `Failed`) along with any partial output captured so far.
+    ///
+    /// The `user_id` is required when talking to the orchestrator to ensure
+    /// the request is routed to the correct user's wrapper.
+    pub async fn cancel_task(&self, task_id: &str, user_id: &str) -> Result<TaskResponse> {
+        let url = format!(
+            "{}/api/v1/tasks/{}/cancel?discord_user_id={}",
+            self.base_url, task_id, user_id
+        );
+        let response = self
+            .client
+            .post(&url)
+            .send()
+            .await
+            .context("Failed to cancel task")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("cancel task", response).await);
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse cancel response")
+    }
+
+    /// Open a persistent WebSocket connection to a task's live event stream
+    /// instead of polling `get_task` in a loop. Each item is one decoded
+    /// `TaskEvent`; the stream ends when the connection closes, which the
+    /// server does once it sends `Done`. If `delegated_token` is set (e.g.
+    /// the caller is running against a shared `target_user_id` wrapper), it
+    /// overrides this client's default bearer token for just this handshake,
+    /// matching `submit_task`'s override semantics.
+    pub async fn stream_task(
+        &self,
+        task_id: &str,
+        user_id: &str,
+        delegated_token: Option<&str>,
+    ) -> Result<impl Stream<Item = Result<TaskEvent>>> {
+        let ws_base = self.base_url.replacen("http", "ws", 1);
+        let url = format!(
+            "{}/api/v1/tasks/{}/stream?discord_user_id={}",
+            ws_base, task_id, user_id
+        );
+
+        let mut request = url
+            .into_client_request()
+            .context("Failed to build task event stream request")?;
+        if let Some(token) = delegated_token.or(self.auth_token.as_deref()) {
+            let value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+                .context("Invalid bearer token for task event stream")?;
+            request.headers_mut().insert(reqwest::header::AUTHORIZATION, value);
+        }
+
+        let (ws_stream, _) = connect_async(request)
+            .await
+            .context("Failed to open task event stream")?;
+
+        let (_, read) = ws_stream.split();
+
+        Ok(read.filter_map(|message| async move {
+            match message {
+                Ok(Message::Text(text)) => Some(recv_typed(&text)),
+                Ok(Message::Close(_)) => None,
+                Ok(_) => None,
+                Err(e) => Some(Err(anyhow::Error::new(e).context("Task event stream error"))),
+            }
+        }))
+    }
+
+    /// Long-poll the wrapper's durable "task-events" queue for up to
+    /// `max_wait` for a batch of up to `batch_size` pending lifecycle
+    /// events. An empty result means the long-poll simply timed out with
+    /// nothing new to deliver, not an error — callers should just poll
+    /// again. Each returned event must be acknowledged with
+    /// `ack_task_event` or the queue will redeliver it.
+    pub async fn poll_task_events(&self, batch_size: u32, max_wait: Duration) -> Result<Vec<TaskEventMessage>> {
+        let url = format!(
+            "{}/api/v1/task-events/poll?batch_size={}&wait_seconds={}",
+            self.base_url,
+            batch_size,
+            max_wait.as_secs()
+        );
         let response = self
             .client
             .get(&url)
+            .timeout(max_wait + Duration::from_secs(10))
             .send()
             .await
-            .context("Failed to get task")?;
+            .context("Failed to poll task events")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to get task ({}): {}", status, body);
+            return Err(status_error("poll task events", response).await);
+        }
+
+        let batch: TaskEventBatch = response.json().await.context("Failed to parse task events response")?;
+        Ok(batch.events)
+    }
+
+    /// Acknowledge a delivered task event so the queue doesn't redeliver it.
+    pub async fn ack_task_event(&self, event_id: &str) -> Result<()> {
+        let url = format!("{}/api/v1/task-events/{}/ack", self.base_url, event_id);
+        let response = self
+            .client
+            .post(&url)
+            .send()
+            .await
+            .context("Failed to ack task event")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("ack task event", response).await);
+        }
+
+        Ok(())
+    }
+
+    /// Drive a cursor-paginated GET endpoint as a lazily-fetched stream:
+    /// yield buffered items from the current page first, and once the
+    /// buffer empties, fetch the next page with `?cursor=...&limit=...`
+    /// until the server stops returning a `next_cursor`.
+    fn paginate<T>(&self, base_url: String) -> impl Stream<Item = Result<T>>
+    where
+        T: serde::de::DeserializeOwned + 'static,
+    {
+        let state = PageState {
+            client: self.clone(),
+            base_url,
+            cursor: None,
+            buffer: std::collections::VecDeque::new(),
+            done: false,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.buffer.pop_front() {
+                    return Some((Ok(item), state));
+                }
+
+                if state.done {
+                    return None;
+                }
+
+                match state.client.fetch_page(&state.base_url, state.cursor.as_deref()).await {
+                    Ok(page) => {
+                        state.buffer = page.items.into_iter().collect();
+                        state.cursor = page.next_cursor;
+                        state.done = state.cursor.is_none();
+                    }
+                    Err(e) => {
+                        state.done = true;
+                        return Some((Err(e), state));
+                    }
+                }
+            }
+        })
+    }
+
+    /// Fetch a single page of a cursor-paginated collection.
+    async fn fetch_page<T>(&self, base_url: &str, cursor: Option<&str>) -> Result<Page<T>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let url = match cursor {
+            Some(cursor) => format!("{}?cursor={}&limit={}", base_url, cursor, PAGE_LIMIT),
+            None => format!("{}?limit={}", base_url, PAGE_LIMIT),
+        };
+        let response = self
+            .send_idempotent(reqwest::Method::GET, &url)
+            .await
+            .context("Failed to fetch page")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("fetch page", response).await);
         }
 
         response
             .json()
             .await
-            .context("Failed to parse task response")
+            .context("Failed to parse page response")
     }
 
     /// Submit an approval response for a task.
@@ -316,29 +911,23 @@ impl WrapperClient {
             .context("Failed to parse approval response")
     }
 
+    /// Lazily stream every active session a page at a time, instead of
+    /// materializing the whole collection up front. Terminates once the
+    /// server stops returning a `next_cursor`.
+    pub fn list_sessions_stream(&self) -> impl Stream<Item = Result<SessionInfo>> {
+        self.paginate(format!("{}/api/v1/sessions", self.base_url))
+    }
+
     /// List all active sessions.
     pub async fn list_sessions(&self) -> Result<Vec<SessionInfo>> {
-        let url = format!("{}/api/v1/sessions", self.base_url);
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to list sessions")?;
-
-        response
-            .json()
-            .await
-            .context("Failed to parse sessions response")
+        self.list_sessions_stream().try_collect().await
     }
 
     /// Terminate a session.
     pub async fn terminate_session(&self, session_id: &str) -> Result<()> {
         let url = format!("{}/api/v1/sessions/{}", self.base_url, session_id);
         let response = self
-            .client
-            .delete(&url)
-            .send()
+            .send_idempotent(reqwest::Method::DELETE, &url)
             .await
             .context("Failed to terminate session")?;
 
@@ -351,37 +940,71 @@ impl WrapperClient {
         Ok(())
     }
 
+    /// Lazily stream a user's registered projects a page at a time, instead
+    /// of materializing the whole collection up front. Terminates once the
+    /// server stops returning a `next_cursor`.
+    pub fn list_projects_stream(&self, discord_user_id: &str) -> impl Stream<Item = Result<ProjectResponse>> {
+        self.paginate(format!("{}/api/v1/projects/{}", self.base_url, discord_user_id))
+    }
+
     /// List all registered projects for a user.
     pub async fn list_projects(&self, discord_user_id: &str) -> Result<Vec<ProjectResponse>> {
-        let url = format!("{}/api/v1/projects/{}", self.base_url, discord_user_id);
+        self.list_projects_stream(discord_user_id).try_collect().await
+    }
+
+    /// Add a new project.
+    pub async fn add_project(&self, request: ProjectRequest) -> Result<ProjectResponse> {
+        let url = format!("{}/api/v1/projects", self.base_url);
         let response = self
             .client
-            .get(&url)
+            .post(&url)
+            .json(&request)
             .send()
             .await
-            .context("Failed to list projects")?;
+            .context("Failed to add project")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("add project", response).await);
+        }
 
         response
             .json()
             .await
-            .context("Failed to parse projects response")
+            .context("Failed to parse project response")
     }
 
-    /// Add a new project.
-    pub async fn add_project(&self, request: ProjectRequest) -> Result<ProjectResponse> {
-        let url = format!("{}/api/v1/projects", self.base_url);
+    /// Remove a project for a user.
+    pub async fn remove_project(&self, discord_user_id: &str, name: &str) -> Result<()> {
+        let url = format!("{}/api/v1/projects/{}/{}", self.base_url, discord_user_id, name);
+        let response = self
+            .send_idempotent(reqwest::Method::DELETE, &url)
+            .await
+            .context("Failed to remove project")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("remove project", response).await);
+        }
+
+        Ok(())
+    }
+
+    /// Share a project with another user, granting them `/task` access
+    /// without making them the owner. Only the current owner may do this.
+    pub async fn share_project(&self, owner_id: &str, name: &str, target_user_id: &str) -> Result<ProjectResponse> {
+        let url = format!("{}/api/v1/projects/{}/{}/share", self.base_url, owner_id, name);
+        let request = ShareProjectRequest {
+            target_user_id: target_user_id.to_string(),
+        };
         let response = self
             .client
             .post(&url)
             .json(&request)
             .send()
             .await
-            .context("Failed to add project")?;
+            .context("Failed to share project")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to add project ({}): {}", status, body);
+            return Err(status_error("share project", response).await);
         }
 
         response
@@ -390,23 +1013,79 @@ impl WrapperClient {
             .context("Failed to parse project response")
     }
 
-    /// Remove a project for a user.
-    pub async fn remove_project(&self, discord_user_id: &str, name: &str) -> Result<()> {
-        let url = format!("{}/api/v1/projects/{}/{}", self.base_url, discord_user_id, name);
+    /// Revoke another user's shared access to a project. Only the current
+    /// owner may do this.
+    pub async fn unshare_project(&self, owner_id: &str, name: &str, target_user_id: &str) -> Result<ProjectResponse> {
+        let url = format!(
+            "{}/api/v1/projects/{}/{}/share/{}",
+            self.base_url, owner_id, name, target_user_id
+        );
+        let response = self
+            .send_idempotent(reqwest::Method::DELETE, &url)
+            .await
+            .context("Failed to unshare project")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("unshare project", response).await);
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse project response")
+    }
+
+    /// Transfer ownership of a project to another user, keeping its existing
+    /// `authorized_ids`. Only the current owner may do this.
+    pub async fn transfer_project(&self, owner_id: &str, name: &str, new_owner_id: &str) -> Result<ProjectResponse> {
+        let url = format!("{}/api/v1/projects/{}/{}/transfer", self.base_url, owner_id, name);
+        let request = TransferProjectRequest {
+            new_owner_id: new_owner_id.to_string(),
+        };
         let response = self
             .client
-            .delete(&url)
+            .post(&url)
+            .json(&request)
             .send()
             .await
-            .context("Failed to remove project")?;
+            .context("Failed to transfer project")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to remove project ({}): {}", status, body);
+            return Err(status_error("transfer project", response).await);
         }
 
-        Ok(())
+        response
+            .json()
+            .await
+            .context("Failed to parse project response")
+    }
+
+    /// Fetch the most recent `limit` activity entries for a project,
+    /// newest-first (registrations, removals, shares, transfers, and task
+    /// runs). `user_id` must be the owner or hold shared access.
+    pub async fn project_history(
+        &self,
+        user_id: &str,
+        name: &str,
+        limit: u32,
+    ) -> Result<ProjectHistoryResponse> {
+        let url = format!(
+            "{}/api/v1/projects/{}/{}/history?limit={}",
+            self.base_url, user_id, name, limit
+        );
+        let response = self
+            .send_idempotent(reqwest::Method::GET, &url)
+            .await
+            .context("Failed to fetch project history")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("fetch project history", response).await);
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse project history response")
     }
 
     // =========================================================================
@@ -417,16 +1096,12 @@ impl WrapperClient {
     pub async fn get_user(&self, discord_id: &str) -> Result<UserResponse> {
         let url = format!("{}/api/v1/users/{}", self.base_url, discord_id);
         let response = self
-            .client
-            .get(&url)
-            .send()
+            .send_idempotent(reqwest::Method::GET, &url)
             .await
             .context("Failed to get user")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to get user ({}): {}", status, body);
+            return Err(status_error("get user", response).await);
         }
 
         response
@@ -435,8 +1110,21 @@ impl WrapperClient {
             .context("Failed to parse user response")
     }
 
-    /// Register a local wrapper for a user.
-    pub async fn register_local(&self, request: RegisterLocalRequest) -> Result<UserResponse> {
+    /// Register a local wrapper for a user. Probes `request.wrapper_url` for
+    /// reachability and declared capabilities before trusting it - the
+    /// probe's error (precise: "connection refused", "timed out after Ns",
+    /// "too many redirects", ...) is returned as-is rather than wrapped, so
+    /// the caller can show it directly instead of a generic failure. If
+    /// `request.auth_token` is set, it's hashed with argon2id here and
+    /// replaced with the hash before the request is ever serialized - the
+    /// raw token never reaches the wire.
+    pub async fn register_local(&self, mut request: RegisterLocalRequest) -> Result<UserResponse> {
+        request.capabilities = Some(probe_wrapper_url(&request.wrapper_url).await?);
+
+        if let Some(token) = request.auth_token.take() {
+            request.auth_token = Some(hash_auth_token(&token).context("Failed to hash auth token")?);
+        }
+
         let url = format!("{}/api/v1/users/register-local", self.base_url);
         let response = self
             .client
@@ -447,9 +1135,7 @@ impl WrapperClient {
             .context("Failed to register local wrapper")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to register local wrapper ({}): {}", status, body);
+            return Err(status_error("register local wrapper", response).await);
         }
 
         response
@@ -458,20 +1144,40 @@ impl WrapperClient {
             .context("Failed to parse user response")
     }
 
+    /// Generate a fresh random auth token for `discord_id`, store its
+    /// argon2id hash on the wrapper service (replacing whatever was there),
+    /// and return the raw token so the caller can show it to the user
+    /// exactly once - the bot doesn't keep its own durable copy either.
+    pub async fn rotate_token(&self, discord_id: &str) -> Result<String> {
+        let token = generate_auth_token();
+        let hash = hash_auth_token(&token).context("Failed to hash auth token")?;
+
+        let url = format!("{}/api/v1/users/{}/rotate-token", self.base_url, discord_id);
+        let response = self
+            .client
+            .post(&url)
+            .json(&RotateTokenRequest { auth_token_hash: hash })
+            .send()
+            .await
+            .context("Failed to rotate auth token")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("rotate auth token", response).await);
+        }
+
+        Ok(token)
+    }
+
     /// Unregister a user's local wrapper.
     pub async fn unregister_local(&self, discord_id: &str) -> Result<()> {
         let url = format!("{}/api/v1/users/{}/local", self.base_url, discord_id);
         let response = self
-            .client
-            .delete(&url)
-            .send()
+            .send_idempotent(reqwest::Method::DELETE, &url)
             .await
             .context("Failed to unregister local wrapper")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to unregister local wrapper ({}): {}", status, body);
+            return Err(status_error("unregister local wrapper", response).await);
         }
 
         Ok(())
@@ -489,9 +1195,7 @@ impl WrapperClient {
             .context("Failed to enable cluster access")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to enable cluster access ({}): {}", status, body);
+            return Err(status_error("enable cluster access", response).await);
         }
 
         response
@@ -513,9 +1217,71 @@ impl WrapperClient {
             .context("Failed to set user mode")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to set user mode ({}): {}", status, body);
+            return Err(status_error("set user mode", response).await);
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse user response")
+    }
+
+    // =========================================================================
+    // Cluster Nodes
+    // =========================================================================
+
+    /// List every node in the cluster, with its declared capabilities and
+    /// current health, for `/register status` to render.
+    pub async fn list_nodes(&self) -> Result<Vec<NodeInfo>> {
+        let url = format!("{}/api/v1/cluster/nodes", self.base_url);
+        let response = self
+            .send_idempotent(reqwest::Method::GET, &url)
+            .await
+            .context("Failed to list cluster nodes")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("list cluster nodes", response).await);
+        }
+
+        let body: NodeListResponse = response.json().await.context("Failed to parse cluster nodes response")?;
+        Ok(body.nodes)
+    }
+
+    /// Ask the orchestrator to deterministically allocate a node for
+    /// `project` (consistent hashing over node ids, so the same project
+    /// keeps landing on the same node as long as it stays reachable).
+    pub async fn allocate_node(&self, project: &str) -> Result<String> {
+        let url = format!("{}/api/v1/cluster/allocate?project={}", self.base_url, project);
+        let response = self
+            .send_idempotent(reqwest::Method::GET, &url)
+            .await
+            .context("Failed to allocate cluster node")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("allocate cluster node", response).await);
+        }
+
+        let body: AllocateNodeResponse = response.json().await.context("Failed to parse allocate response")?;
+        Ok(body.node_id)
+    }
+
+    /// Pin `discord_id`'s cluster-mode tasks to a specific node, overriding
+    /// the orchestrator's default allocation.
+    pub async fn set_cluster_node(&self, discord_id: &str, node_id: &str) -> Result<UserResponse> {
+        let url = format!("{}/api/v1/users/{}/cluster-node", self.base_url, discord_id);
+        let request = SetClusterNodeRequest {
+            node_id: node_id.to_string(),
+        };
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to set cluster node")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("set cluster node", response).await);
         }
 
         response
@@ -543,9 +1309,7 @@ impl WrapperClient {
             .context("Failed to share wrapper")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to share wrapper ({}): {}", status, body);
+            return Err(status_error("share wrapper", response).await);
         }
 
         response
@@ -561,16 +1325,12 @@ impl WrapperClient {
             self.base_url, owner_id, target_id
         );
         let response = self
-            .client
-            .delete(&url)
-            .send()
+            .send_idempotent(reqwest::Method::DELETE, &url)
             .await
             .context("Failed to unshare wrapper")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to unshare wrapper ({}): {}", status, body);
+            return Err(status_error("unshare wrapper", response).await);
         }
 
         response
@@ -583,16 +1343,12 @@ impl WrapperClient {
     pub async fn list_shared(&self, owner_id: &str) -> Result<ShareListResponse> {
         let url = format!("{}/api/v1/users/{}/share", self.base_url, owner_id);
         let response = self
-            .client
-            .get(&url)
-            .send()
+            .send_idempotent(reqwest::Method::GET, &url)
             .await
             .context("Failed to list shared users")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to list shared users ({}): {}", status, body);
+            return Err(status_error("list shared users", response).await);
         }
 
         response
@@ -601,6 +1357,31 @@ impl WrapperClient {
             .context("Failed to parse share list response")
     }
 
+    /// Transfer ownership of a wrapper (and its existing share list) from
+    /// `current_owner_id` to `new_owner_id`.
+    pub async fn transfer_ownership(&self, current_owner_id: &str, new_owner_id: &str) -> Result<UserResponse> {
+        let url = format!("{}/api/v1/users/{}/transfer", self.base_url, current_owner_id);
+        let request = TransferOwnershipRequest {
+            new_owner_id: new_owner_id.to_string(),
+        };
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to transfer wrapper ownership")?;
+
+        if !response.status().is_success() {
+            return Err(status_error("transfer wrapper ownership", response).await);
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse transfer response")
+    }
+
     /// List all wrappers the user can access.
     pub async fn list_accessible_wrappers(&self, user_id: &str) -> Result<AccessibleWrappersResponse> {
         let url = format!(
@@ -608,16 +1389,12 @@ impl WrapperClient {
             self.base_url, user_id
         );
         let response = self
-            .client
-            .get(&url)
-            .send()
+            .send_idempotent(reqwest::Method::GET, &url)
             .await
             .context("Failed to list accessible wrappers")?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to list accessible wrappers ({}): {}", status, body);
+            return Err(status_error("list accessible wrappers", response).await);
         }
 
         response
@@ -626,3 +1403,46 @@ impl WrapperClient {
             .context("Failed to parse accessible wrappers response")
     }
 }
+
+/// Decode one WebSocket text frame into a `TaskEvent`.
+fn recv_typed(text: &str) -> Result<TaskEvent> {
+    serde_json::from_str(text).context("Failed to parse task event")
+}
+
+/// Turn a non-success response into an `anyhow::Error`, calling out 401/403
+/// as an authorization problem (expired or missing bearer token) rather than
+/// folding it into the generic status-body bail, so callers can prompt the
+/// user to re-share or refresh instead of just showing a raw HTTP error.
+async fn status_error(action: &str, response: reqwest::Response) -> anyhow::Error {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return anyhow::anyhow!(
+            "Wrapper access not authorized or token expired while trying to {} ({}). \
+             Ask the wrapper owner to re-share access with you, or re-register with a fresh token.",
+            action,
+            status
+        );
+    }
+
+    anyhow::anyhow!("Failed to {} ({}): {}", action, status, body)
+}
+
+/// Read a header as a `u32`, if present and parseable.
+fn header_u32(headers: &HeaderMap, name: &str) -> Option<u32> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// Parse a `Retry-After` header, in either delta-seconds or HTTP-date form,
+/// into how long to wait from now.
+fn retry_after_delay(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = httpdate::parse_http_date(value).ok()?;
+    target.duration_since(SystemTime::now()).ok()
+}
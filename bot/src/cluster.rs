@@ -0,0 +1,135 @@
+//! Cluster node metadata and deterministic task-to-node allocation.
+//!
+//! Distinct from `client::ClusterClient` (which load-balances once a node
+//! has already been chosen for a session): this module answers "which node
+//! should this project land on" from a read-only metadata snapshot loaded
+//! once at startup, combined with a live view of which of those nodes are
+//! currently reachable. `WrapperClient`'s own `list_nodes`/`allocate_node`
+//! are backed by the orchestrator instead; `/register status` calls
+//! `list_nodes` directly. `dispatch::run` is what uses this module's allocation, for jobs
+//! that bypass the orchestrator entirely. `client::ClusterClient` (via
+//! `/cluster status`) is the third leg: talking directly to each node's own
+//! `base_url` for a concurrent health fan-out, independent of whether the
+//! orchestrator considers the node reachable.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, RwLock};
+
+use anyhow::{Context, Result};
+use futures_util::stream::FuturesUnordered;
+use futures_util::StreamExt;
+use serde::Deserialize;
+
+use crate::client::WrapperClient;
+
+/// One node's static description, as declared in the cluster metadata file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClusterNodeInfo {
+    pub node_id: String,
+    pub address: String,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    pub owning_discord_id: String,
+}
+
+/// Read-only snapshot of every node in the cluster, loaded once at startup
+/// and never mutated afterward.
+#[derive(Debug, Clone)]
+pub struct ClusterMetadata {
+    nodes: Vec<ClusterNodeInfo>,
+}
+
+impl ClusterMetadata {
+    /// Load from a JSON file of `ClusterNodeInfo` entries. A missing or
+    /// empty file is valid - it just means nobody can pick
+    /// `ExecutionMode::Cluster` yet.
+    pub fn load(path: &str) -> Result<Self> {
+        let data = match std::fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::empty()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read cluster metadata file '{}'", path))
+            }
+        };
+        if data.trim().is_empty() {
+            return Ok(Self::empty());
+        }
+        let nodes: Vec<ClusterNodeInfo> = serde_json::from_str(&data)
+            .with_context(|| format!("Failed to parse cluster metadata file '{}'", path))?;
+        Ok(Self { nodes })
+    }
+
+    pub fn empty() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    pub fn nodes(&self) -> &[ClusterNodeInfo] {
+        &self.nodes
+    }
+}
+
+/// Live reachability of every node in a `ClusterMetadata`, refreshed by
+/// polling each node's health endpoint, plus the deterministic allocation
+/// function built on top of that live view.
+#[derive(Debug, Clone)]
+pub struct NodeRegistry {
+    metadata: ClusterMetadata,
+    reachable: Arc<RwLock<HashMap<String, bool>>>,
+}
+
+impl NodeRegistry {
+    /// Every node starts assumed-reachable until the first `refresh`.
+    pub fn new(metadata: ClusterMetadata) -> Self {
+        let reachable = metadata.nodes().iter().map(|n| (n.node_id.clone(), true)).collect();
+        Self {
+            metadata,
+            reachable: Arc::new(RwLock::new(reachable)),
+        }
+    }
+
+    pub fn metadata(&self) -> &ClusterMetadata {
+        &self.metadata
+    }
+
+    /// Health-check every node concurrently and refresh the reachable set.
+    pub async fn refresh(&self) {
+        let mut checks = FuturesUnordered::new();
+        for node in self.metadata.nodes() {
+            let node_id = node.node_id.clone();
+            let client = WrapperClient::new(&node.address);
+            checks.push(async move { (node_id, client.health_check().await.is_ok()) });
+        }
+
+        let mut reachable = self.reachable.write().unwrap();
+        while let Some((node_id, ok)) = checks.next().await {
+            reachable.insert(node_id, ok);
+        }
+    }
+
+    pub fn is_reachable(&self, node_id: &str) -> bool {
+        self.reachable.read().unwrap().get(node_id).copied().unwrap_or(false)
+    }
+
+    /// Deterministically map `project` to one of the currently-reachable
+    /// nodes, via consistent hashing over node ids: the node whose id hashes
+    /// closest (clockwise) to the project's hash wins. The same project
+    /// keeps landing on the same node as long as that node stays reachable
+    /// and the node set doesn't change.
+    pub fn allocate_node(&self, project: &str) -> Option<String> {
+        let key_hash = hash_str(project);
+        self.metadata
+            .nodes()
+            .iter()
+            .filter(|n| self.is_reachable(&n.node_id))
+            .min_by_key(|n| hash_str(&n.node_id).wrapping_sub(key_hash))
+            .map(|n| n.node_id.clone())
+    }
+}
+
+fn hash_str(s: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
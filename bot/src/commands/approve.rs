@@ -1,12 +1,52 @@
 //! /approve command - Submit approval for a task requiring human intervention.
 
 use serenity::all::{
-    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
-    CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse,
+    ButtonStyle, CommandInteraction, CommandOptionType, ComponentInteraction, Context,
+    CreateActionRow, CreateButton, CreateCommand, CreateCommandOption,
+    CreateInputText, CreateInteractionResponse, CreateModal, EditInteractionResponse, EditMessage,
+    InputTextStyle, ModalInteraction,
 };
 use tracing::{error, info};
 
-use crate::client::{ApprovalSubmission, TaskStatus, WrapperClient};
+use crate::client::{ApprovalOption, ApprovalRequest, ApprovalSubmission, TaskStatus, WrapperClient};
+use crate::commands::truncate_chars;
+use crate::db::TaskStore;
+
+/// Option IDs carrying this prefix open a free-text modal instead of
+/// submitting immediately, so the caller can type a custom response.
+const CUSTOM_OPTION_PREFIX: &str = "custom";
+
+/// Build the action row of buttons for an approval request, one button per
+/// option. The `custom_id` encodes both the task ID and the option ID so the
+/// component handler can resolve which approval this click belongs to.
+pub fn build_approval_components(task_id: &str, approval: &ApprovalRequest) -> Vec<CreateActionRow> {
+    let buttons: Vec<CreateButton> = approval
+        .options
+        .iter()
+        .map(|option| approval_button(task_id, option))
+        .collect();
+
+    // Discord caps an action row at 5 buttons, so the buttons are laid out
+    // across as many rows as the options need, 5 per row.
+    buttons
+        .chunks(5)
+        .map(|chunk| CreateActionRow::Buttons(chunk.to_vec()))
+        .collect()
+}
+
+fn approval_button(task_id: &str, option: &ApprovalOption) -> CreateButton {
+    let style = if option.id.starts_with(CUSTOM_OPTION_PREFIX) {
+        ButtonStyle::Secondary
+    } else if option.id == "deny" || option.id == "reject" {
+        ButtonStyle::Danger
+    } else {
+        ButtonStyle::Primary
+    };
+
+    CreateButton::new(format!("approve:{}:{}", task_id, option.id))
+        .label(&option.label)
+        .style(style)
+}
 
 /// Create the command registration.
 pub fn register() -> CreateCommand {
@@ -73,16 +113,9 @@ pub async fn approve(ctx: &Context, command: &CommandInteraction, wrapper: &Wrap
         task_id, option_id, user_id, custom_response
     );
 
-    // Send initial "processing" response
-    let initial_response = CreateInteractionResponseMessage::new()
-        .content("⏳ Processing approval...")
-        .ephemeral(false);
-
-    if let Err(e) = command
-        .create_response(&ctx.http, CreateInteractionResponse::Message(initial_response))
-        .await
-    {
-        error!("Failed to send initial response: {}", e);
+    // Defer immediately so the wrapper round-trip below can't blow past
+    // Discord's 3-second initial-response window.
+    if crate::commands::defer(ctx, command, false).await.is_err() {
         return;
     }
 
@@ -94,43 +127,7 @@ pub async fn approve(ctx: &Context, command: &CommandInteraction, wrapper: &Wrap
 
     match wrapper.submit_approval(task_id, &user_id, submission).await {
         Ok(response) => {
-            let status_emoji = match response.status {
-                TaskStatus::Completed => "✅",
-                TaskStatus::Failed => "❌",
-                TaskStatus::Running => "🔄",
-                TaskStatus::Pending => "⏳",
-                TaskStatus::NeedsApproval => "⚠️",
-            };
-
-            let mut content = format!(
-                "{} **Approval Processed**\n\n**Status:** {}\n**Task ID:** `{}`",
-                status_emoji, response.status, response.task_id,
-            );
-
-            // Add output if present
-            if !response.output.is_empty() {
-                let output = if response.output.len() > 1800 {
-                    format!("{}...\n(truncated)", &response.output[..1800])
-                } else {
-                    response.output.clone()
-                };
-                content.push_str(&format!("\n\n**Output:**\n```\n{}\n```", output));
-            }
-
-            // Add error if present
-            if let Some(err) = &response.error {
-                content.push_str(&format!("\n\n**Error:**\n```\n{}\n```", err));
-            }
-
-            // Check if more approval is needed
-            if let Some(approval) = &response.approval_request {
-                content.push_str(&format!(
-                    "\n\n**Additional Approval Required:**\n{}\n\nUse `/approve task_id:{} option:<option>` to respond.",
-                    approval.description,
-                    response.task_id,
-                ));
-            }
-
+            let content = render_approval_result(&response);
             let edit = EditInteractionResponse::new().content(content);
             if let Err(e) = command.edit_response(&ctx.http, edit).await {
                 error!("Failed to edit response: {}", e);
@@ -146,3 +143,214 @@ pub async fn approve(ctx: &Context, command: &CommandInteraction, wrapper: &Wrap
         }
     }
 }
+
+/// Render a processed approval's `TaskResponse` as message content, shared by
+/// both the typed `/approve` command and the button/modal component handlers.
+fn render_approval_result(response: &crate::client::TaskResponse) -> String {
+    let status_emoji = match response.status {
+        TaskStatus::Completed => "✅",
+        TaskStatus::Failed => "❌",
+        TaskStatus::Running => "🔄",
+        TaskStatus::Pending => "⏳",
+        TaskStatus::NeedsApproval => "⚠️",
+    };
+
+    let mut content = format!(
+        "{} **Approval Processed**\n\n**Status:** {}\n**Task ID:** `{}`",
+        status_emoji, response.status, response.task_id,
+    );
+
+    if !response.output.is_empty() {
+        let output = if response.output.chars().count() > 1800 {
+            format!("{}...\n(truncated)", truncate_chars(&response.output, 1800))
+        } else {
+            response.output.clone()
+        };
+        content.push_str(&format!("\n\n**Output:**\n```\n{}\n```", output));
+    }
+
+    if let Some(err) = &response.error {
+        content.push_str(&format!("\n\n**Error:**\n```\n{}\n```", err));
+    }
+
+    if let Some(approval) = &response.approval_request {
+        content.push_str(&format!(
+            "\n\n**Additional Approval Required:**\n{}",
+            approval.description,
+        ));
+    }
+
+    content
+}
+
+/// Whether `user_id` may respond to `task_id`'s approval: checks the
+/// in-memory tracker first (no DB round-trip for the common case of a task
+/// submitted this process lifetime), falling back to the durable
+/// `TaskStore` when it isn't tracked - e.g. the bot restarted since the
+/// task was submitted, or it was submitted by a prior instance. Without the
+/// fallback, a restart would make `task_is_owner` return false for
+/// everyone and permanently lock the real owner out. The wrapper
+/// re-checks this server-side regardless; this only saves a pointless
+/// round-trip (and a confusing "not authorized" error) for the common case
+/// of someone else clicking.
+async fn is_owner(db: &TaskStore, task_id: &str, user_id: &str) -> bool {
+    if crate::commands::task_is_owner(task_id, user_id) {
+        return true;
+    }
+
+    match db.notify_target(task_id).await {
+        Ok(Some((_, owner_id))) => owner_id == user_id,
+        Ok(None) => false,
+        Err(e) => {
+            error!("Failed to look up task owner for {}: {}", task_id, e);
+            false
+        }
+    }
+}
+
+/// Handle a button click on an approval action row (`custom_id` format
+/// `approve:<task_id>:<option_id>`). Options whose ID starts with
+/// [`CUSTOM_OPTION_PREFIX`] open a modal to collect free-text instead of
+/// submitting immediately.
+pub async fn handle_component(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    wrapper: &WrapperClient,
+    db: &TaskStore,
+) {
+    let Some((task_id, option_id)) = parse_custom_id(&interaction.data.custom_id) else {
+        return;
+    };
+
+    let user_id = interaction.user.id.to_string();
+
+    if !is_owner(db, &task_id, &user_id).await {
+        if let Err(e) = interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    serenity::all::CreateInteractionResponseMessage::new()
+                        .content("❌ Only the user who submitted this task can respond to its approval.")
+                        .ephemeral(true),
+                ),
+            )
+            .await
+        {
+            error!("Failed to reject unauthorized approval click: {}", e);
+        }
+        return;
+    }
+
+    if option_id.starts_with(CUSTOM_OPTION_PREFIX) {
+        let modal_custom_id = format!("approve_modal:{}:{}", task_id, option_id);
+        let modal = CreateModal::new(modal_custom_id, "Custom Approval Response").components(vec![
+            CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Paragraph, "Response", "response")
+                    .placeholder("Describe what you'd like Claude to do instead")
+                    .required(true),
+            ),
+        ]);
+
+        if let Err(e) = interaction
+            .create_response(&ctx.http, CreateInteractionResponse::Modal(modal))
+            .await
+        {
+            error!("Failed to open approval modal: {}", e);
+        }
+        return;
+    }
+
+    if let Err(e) = interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+        .await
+    {
+        error!("Failed to acknowledge approval button: {}", e);
+        return;
+    }
+
+    let submission = ApprovalSubmission {
+        option_id: option_id.to_string(),
+        custom_response: None,
+    };
+
+    submit_and_update_message(ctx, interaction.message.id, interaction.channel_id, &task_id, &user_id, submission, wrapper).await;
+}
+
+/// Handle the free-text modal submitted after a "custom" approval button.
+pub async fn handle_modal_submit(
+    ctx: &Context,
+    interaction: &ModalInteraction,
+    wrapper: &WrapperClient,
+    db: &TaskStore,
+) {
+    let Some(rest) = interaction.data.custom_id.strip_prefix("approve_modal:") else {
+        return;
+    };
+    let Some((task_id, option_id)) = rest.split_once(':') else {
+        return;
+    };
+
+    let custom_response = interaction
+        .data
+        .components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            serenity::all::ActionRowComponent::InputText(input) => input.value.clone(),
+            _ => None,
+        });
+
+    let user_id = interaction.user.id.to_string();
+
+    if !is_owner(db, task_id, &user_id).await {
+        return;
+    }
+
+    if let Err(e) = interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+        .await
+    {
+        error!("Failed to acknowledge approval modal: {}", e);
+        return;
+    }
+
+    let Some(message) = interaction.message.as_ref() else {
+        return;
+    };
+
+    let submission = ApprovalSubmission {
+        option_id: option_id.to_string(),
+        custom_response,
+    };
+
+    submit_and_update_message(ctx, message.id, interaction.channel_id, task_id, &user_id, submission, wrapper).await;
+}
+
+async fn submit_and_update_message(
+    ctx: &Context,
+    message_id: serenity::all::MessageId,
+    channel_id: serenity::all::ChannelId,
+    task_id: &str,
+    user_id: &str,
+    submission: ApprovalSubmission,
+    wrapper: &WrapperClient,
+) {
+    let content = match wrapper.submit_approval(task_id, user_id, submission).await {
+        Ok(response) => render_approval_result(&response),
+        Err(e) => {
+            error!("Approval submission failed: {}", e);
+            format!("❌ **Approval Failed**\n\n```\n{}\n```", e)
+        }
+    };
+
+    let edit = EditMessage::new().content(content).components(vec![]);
+    if let Err(e) = channel_id.edit_message(&ctx.http, message_id, edit).await {
+        error!("Failed to update approval message: {}", e);
+    }
+}
+
+fn parse_custom_id(custom_id: &str) -> Option<(String, String)> {
+    let rest = custom_id.strip_prefix("approve:")?;
+    let (task_id, option_id) = rest.split_once(':')?;
+    Some((task_id.to_string(), option_id.to_string()))
+}
@@ -0,0 +1,61 @@
+//! /cluster command - health-check every node in the Pi cluster directly.
+
+use std::collections::HashMap;
+
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use tracing::error;
+
+use crate::client::{ClusterClient, NodeStatus};
+
+/// Create the command registration.
+pub fn register() -> CreateCommand {
+    CreateCommand::new("cluster").description("Inspect the Pi cluster").add_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "status",
+            "Health-check every cluster node directly and report its status",
+        ),
+    )
+}
+
+/// Handle the /cluster command. Only `status` exists today, so there's
+/// nothing to dispatch on yet.
+pub async fn cluster(ctx: &Context, command: &CommandInteraction, cluster_client: &ClusterClient) {
+    let statuses = cluster_client.broadcast_health().await;
+
+    let content = if statuses.is_empty() {
+        "No cluster nodes are configured.".to_string()
+    } else {
+        format!("**Cluster Status**\n\n{}", render_statuses(&statuses))
+    };
+
+    let response = CreateInteractionResponseMessage::new().content(content);
+    if let Err(e) = command
+        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+        .await
+    {
+        error!("Failed to send cluster status response: {}", e);
+    }
+}
+
+/// Render `broadcast_health`'s per-node map as Discord message content,
+/// node ids sorted for a stable, diffable listing.
+fn render_statuses(statuses: &HashMap<String, NodeStatus>) -> String {
+    let mut node_ids: Vec<&String> = statuses.keys().collect();
+    node_ids.sort();
+
+    node_ids
+        .into_iter()
+        .map(|node_id| match &statuses[node_id] {
+            NodeStatus::Healthy(health) => format!(
+                "✅ `{}` - v{} (up {}s)",
+                node_id, health.version, health.uptime_seconds as u64
+            ),
+            NodeStatus::Unreachable(err) => format!("❌ `{}` - unreachable: {}", node_id, err),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,257 @@
+//! Pre/post hooks that run around every command dispatch.
+//!
+//! Centralizes cross-cutting behavior — rate limiting, audit logging, guild
+//! restrictions — so it doesn't have to be copy-pasted into each command
+//! handler. A `before` hook can reject the command outright (`Err`); `after`
+//! hooks always run once dispatch has decided the command's outcome.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serenity::all::{CommandInteraction, Context};
+use serenity::async_trait;
+use tracing::info;
+use uuid::Uuid;
+
+/// Error returned by a `before` hook to short-circuit dispatch. The message
+/// is shown to the user verbatim in an ephemeral reply.
+#[derive(Debug, Clone)]
+pub struct HookError(pub String);
+
+impl fmt::Display for HookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for HookError {
+    fn from(message: String) -> Self {
+        Self(message)
+    }
+}
+
+/// What happened to a dispatched command, passed to `after` hooks so they
+/// can tell a completed run apart from one a `before` hook turned away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandOutcome {
+    Executed,
+    Rejected,
+}
+
+/// Runs before and after every dispatched command.
+#[async_trait]
+pub trait CommandHook: Send + Sync {
+    /// Called before the command executor. Returning `Err` short-circuits
+    /// dispatch; the error's message is shown to the user.
+    async fn before(&self, _ctx: &Context, _command: &CommandInteraction) -> Result<(), HookError> {
+        Ok(())
+    }
+
+    /// Called after dispatch has resolved, whether or not the executor ran.
+    async fn after(&self, _ctx: &Context, _command: &CommandInteraction, _outcome: CommandOutcome) {}
+}
+
+/// Per-user token-bucket-ish rate limiter: at most `max_per_window` commands
+/// within `window`, tracked as a sliding log of recent call timestamps.
+pub struct RateLimitHook {
+    max_per_window: u32,
+    window: Duration,
+    recent_calls: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl RateLimitHook {
+    pub fn new(max_per_window: u32, window: Duration) -> Self {
+        Self {
+            max_per_window,
+            window,
+            recent_calls: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandHook for RateLimitHook {
+    async fn before(&self, _ctx: &Context, command: &CommandInteraction) -> Result<(), HookError> {
+        let user_id = command.user.id.to_string();
+        let now = Instant::now();
+
+        let mut recent_calls = self.recent_calls.lock().unwrap();
+        let calls = recent_calls.entry(user_id).or_default();
+
+        while calls.front().is_some_and(|t| now.duration_since(*t) > self.window) {
+            calls.pop_front();
+        }
+
+        if calls.len() as u32 >= self.max_per_window {
+            return Err(HookError(format!(
+                "You're doing that too often. Try again in a bit (limit: {} per {}s).",
+                self.max_per_window,
+                self.window.as_secs()
+            )));
+        }
+
+        calls.push_back(now);
+        Ok(())
+    }
+}
+
+/// Emits a structured `tracing` record of who invoked which command, and
+/// what subcommand (if any) they asked for. Each invocation gets a
+/// generated request UUID, logged on both the `before` and `after` record so
+/// the two can be correlated in log aggregation even when commands overlap.
+#[derive(Default)]
+pub struct AuditLogHook {
+    /// Request UUID for each in-flight interaction, keyed by Discord's
+    /// interaction snowflake (unique per invocation) since hooks have no
+    /// other way to carry state from `before` to `after`.
+    request_ids: Mutex<HashMap<u64, String>>,
+}
+
+#[async_trait]
+impl CommandHook for AuditLogHook {
+    async fn before(&self, _ctx: &Context, command: &CommandInteraction) -> Result<(), HookError> {
+        let request_id = Uuid::new_v4().to_string();
+        self.request_ids.lock().unwrap().insert(command.id.get(), request_id.clone());
+
+        let subcommand = command.data.options.first().map(|opt| opt.name.as_str());
+        info!(
+            request_id = %request_id,
+            command = %command.data.name,
+            subcommand = subcommand.unwrap_or("-"),
+            user_id = %command.user.id,
+            user_name = %command.user.name,
+            "command invoked"
+        );
+        Ok(())
+    }
+
+    async fn after(&self, _ctx: &Context, command: &CommandInteraction, outcome: CommandOutcome) {
+        let request_id = self.request_ids.lock().unwrap().remove(&command.id.get());
+
+        info!(
+            request_id = request_id.as_deref().unwrap_or("-"),
+            command = %command.data.name,
+            user_id = %command.user.id,
+            outcome = ?outcome,
+            "command finished"
+        );
+    }
+}
+
+/// Restricts commands to an allowlist of guilds and/or channels. Either list
+/// being empty means that dimension is unrestricted; a command run in a DM
+/// (no `guild_id`) is rejected whenever a guild allowlist is configured.
+pub struct GuildChannelAllowlistHook {
+    guild_ids: Vec<u64>,
+    channel_ids: Vec<u64>,
+}
+
+impl GuildChannelAllowlistHook {
+    pub fn new(guild_ids: Vec<u64>, channel_ids: Vec<u64>) -> Self {
+        Self { guild_ids, channel_ids }
+    }
+}
+
+#[async_trait]
+impl CommandHook for GuildChannelAllowlistHook {
+    async fn before(&self, _ctx: &Context, command: &CommandInteraction) -> Result<(), HookError> {
+        if !self.guild_ids.is_empty() {
+            let allowed = command.guild_id.is_some_and(|g| self.guild_ids.contains(&g.get()));
+            if !allowed {
+                return Err(HookError("This command isn't available in this server.".to_string()));
+            }
+        }
+
+        if !self.channel_ids.is_empty() && !self.channel_ids.contains(&command.channel_id.get()) {
+            return Err(HookError("This command isn't available in this channel.".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Blocks specific `(command, subcommand)` pairs unless the invoking member
+/// holds `ADMINISTRATOR` or one of `admin_role_ids` - e.g. gating
+/// `/register cluster` so "enabled by admin" is actually enforced, instead
+/// of relying on each handler to remember to check. Subcommand-less
+/// commands (or subcommands not in the gate list) are untouched.
+pub struct AdminGatedSubcommandHook {
+    gated: Vec<(String, String)>,
+    admin_role_ids: Vec<u64>,
+}
+
+impl AdminGatedSubcommandHook {
+    pub fn new(gated: Vec<(String, String)>, admin_role_ids: Vec<u64>) -> Self {
+        Self { gated, admin_role_ids }
+    }
+
+    fn is_admin(&self, command: &CommandInteraction) -> bool {
+        let Some(member) = &command.member else {
+            return false;
+        };
+
+        member.permissions.is_some_and(|p| p.administrator())
+            || member.roles.iter().any(|r| self.admin_role_ids.contains(&r.get()))
+    }
+}
+
+#[async_trait]
+impl CommandHook for AdminGatedSubcommandHook {
+    async fn before(&self, _ctx: &Context, command: &CommandInteraction) -> Result<(), HookError> {
+        let Some(subcommand) = command.data.options.first().map(|opt| opt.name.as_str()) else {
+            return Ok(());
+        };
+
+        let is_gated = self
+            .gated
+            .iter()
+            .any(|(cmd, sub)| cmd == &command.data.name && sub == subcommand);
+
+        if is_gated && !self.is_admin(command) {
+            return Err(HookError(format!(
+                "`/{} {}` is admin-only.",
+                command.data.name, subcommand
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Global maintenance-mode gate: when `enabled`, rejects every command
+/// except from an administrator (holds `ADMINISTRATOR` or one of
+/// `admin_role_ids`), so the bot can be taken offline for maintenance
+/// without a restart while admins keep working.
+pub struct MaintenanceModeHook {
+    enabled: bool,
+    admin_role_ids: Vec<u64>,
+}
+
+impl MaintenanceModeHook {
+    pub fn new(enabled: bool, admin_role_ids: Vec<u64>) -> Self {
+        Self { enabled, admin_role_ids }
+    }
+
+    fn is_admin(&self, command: &CommandInteraction) -> bool {
+        let Some(member) = &command.member else {
+            return false;
+        };
+
+        member.permissions.is_some_and(|p| p.administrator())
+            || member.roles.iter().any(|r| self.admin_role_ids.contains(&r.get()))
+    }
+}
+
+#[async_trait]
+impl CommandHook for MaintenanceModeHook {
+    async fn before(&self, _ctx: &Context, command: &CommandInteraction) -> Result<(), HookError> {
+        if self.enabled && !self.is_admin(command) {
+            return Err(HookError(
+                "The bot is in maintenance mode right now. Please try again later.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
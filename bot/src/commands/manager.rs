@@ -0,0 +1,189 @@
+//! Command registry and dispatcher.
+//!
+//! Centralizes the name -> handler wiring that used to live as a hardcoded
+//! `match` in `Handler::interaction_create`, so adding a command means
+//! registering it here once instead of editing the builder list, the
+//! dispatch match, and (previously) the registration call site.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serenity::all::{
+    Command, CommandInteraction, Context, CreateCommand, CreateInteractionResponse,
+    CreateInteractionResponseMessage, GuildId, Ready,
+};
+use tracing::{error, info};
+
+use crate::client::{ClusterClient, WrapperClient};
+use crate::commands::hooks::{
+    AdminGatedSubcommandHook, AuditLogHook, CommandHook, CommandOutcome, GuildChannelAllowlistHook,
+    MaintenanceModeHook, RateLimitHook,
+};
+use crate::commands::{approve, cluster, project, register, share, status, task, tasks};
+use crate::config::Config;
+use crate::db::TaskStore;
+use crate::jobs::JobStore;
+
+type CommandFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+type CommandExecutor = for<'a> fn(
+    &'a Context,
+    &'a CommandInteraction,
+    &'a WrapperClient,
+    &'a Config,
+    &'a TaskStore,
+    &'a JobStore,
+    &'a ClusterClient,
+) -> CommandFuture<'a>;
+
+/// Registration builder plus the executor it dispatches to.
+struct CommandInfo {
+    builder: CreateCommand,
+    executor: CommandExecutor,
+}
+
+/// Holds every slash command's registration and executor, keyed by name,
+/// plus the before/after hooks that run around each dispatch.
+pub struct CommandManager {
+    commands: HashMap<String, CommandInfo>,
+    hooks: Vec<Arc<dyn CommandHook>>,
+}
+
+impl CommandManager {
+    /// Build the registry with all known commands and default hooks wired in.
+    pub fn new(config: &Config) -> Self {
+        let mut commands = HashMap::new();
+
+        macro_rules! register_command {
+            ($name:expr, $builder:expr, $executor:expr) => {
+                commands.insert(
+                    $name.to_string(),
+                    CommandInfo {
+                        builder: $builder,
+                        executor: $executor,
+                    },
+                );
+            };
+        }
+
+        register_command!("task", task::register(), |ctx, command, wrapper, _config, db, jobs, _cluster| {
+            Box::pin(task::task(ctx, command, wrapper, db, jobs))
+        });
+        register_command!("tasks", tasks::register(), |ctx, command, _wrapper, _config, db, _jobs, _cluster| {
+            Box::pin(tasks::tasks(ctx, command, db))
+        });
+        register_command!("status", status::register(), |ctx, command, wrapper, _config, _db, _jobs, _cluster| {
+            Box::pin(status::status(ctx, command, wrapper))
+        });
+        register_command!("approve", approve::register(), |ctx, command, wrapper, _config, _db, _jobs, _cluster| {
+            Box::pin(approve::approve(ctx, command, wrapper))
+        });
+        register_command!("project", project::register(), |ctx, command, wrapper, config, _db, _jobs, _cluster| {
+            Box::pin(project::project(ctx, command, wrapper, config))
+        });
+        register_command!("register", register::register(), |ctx, command, wrapper, config, _db, jobs, _cluster| {
+            Box::pin(register::handle_register(ctx, command, wrapper, config, jobs))
+        });
+        register_command!("share", share::register(), |ctx, command, wrapper, config, _db, _jobs, _cluster| {
+            Box::pin(share::share(ctx, command, wrapper, config))
+        });
+        register_command!("cluster", cluster::register(), |ctx, command, _wrapper, _config, _db, _jobs, cluster_client| {
+            Box::pin(cluster::cluster(ctx, command, cluster_client))
+        });
+
+        let hooks: Vec<Arc<dyn CommandHook>> = vec![
+            Arc::new(MaintenanceModeHook::new(config.maintenance_mode, config.admin_role_ids.clone())),
+            Arc::new(AuditLogHook::default()),
+            // Generous defaults; `/task` is the only genuinely expensive
+            // command today but the limit applies uniformly for simplicity.
+            Arc::new(RateLimitHook::new(10, Duration::from_secs(60))),
+            Arc::new(GuildChannelAllowlistHook::new(
+                config.allowed_guild_ids.clone(),
+                config.allowed_channel_ids.clone(),
+            )),
+            // `/register cluster` pins tasks to a Pi node; the docs already
+            // describe cluster access as "enabled by admin" so enforce it.
+            Arc::new(AdminGatedSubcommandHook::new(
+                vec![("register".to_string(), "cluster".to_string())],
+                config.admin_role_ids.clone(),
+            )),
+        ];
+
+        Self { commands, hooks }
+    }
+
+    /// Register every known command with Discord, to a single guild (faster
+    /// propagation, useful in development) or globally.
+    pub async fn register_with_discord(&self, ctx: &Context, ready: &Ready, guild_id: Option<u64>) {
+        info!("Registering slash commands...");
+
+        let builders: Vec<CreateCommand> = self.commands.values().map(|info| info.builder.clone()).collect();
+
+        if let Some(gid) = guild_id {
+            let guild = GuildId::new(gid);
+            match guild.set_commands(&ctx.http, builders).await {
+                Ok(cmds) => info!("Registered {} guild commands", cmds.len()),
+                Err(e) => error!("Failed to register guild commands: {}", e),
+            }
+        } else {
+            match Command::set_global_commands(&ctx.http, builders).await {
+                Ok(cmds) => info!("Registered {} global commands", cmds.len()),
+                Err(e) => error!("Failed to register global commands: {}", e),
+            }
+        }
+
+        info!("{} is connected!", ready.user.name);
+    }
+
+    /// Dispatch an incoming command interaction to its registered executor,
+    /// running all `before`/`after` hooks around it. Returns `false` if no
+    /// command with that name is registered.
+    pub async fn dispatch(
+        &self,
+        ctx: &Context,
+        command: &CommandInteraction,
+        wrapper: &WrapperClient,
+        config: &Config,
+        db: &TaskStore,
+        jobs: &JobStore,
+        cluster_client: &ClusterClient,
+    ) -> bool {
+        let Some(info) = self.commands.get(command.data.name.as_str()) else {
+            return false;
+        };
+
+        for hook in &self.hooks {
+            if let Err(err) = hook.before(ctx, command).await {
+                reject(ctx, command, &err.to_string()).await;
+                for hook in &self.hooks {
+                    hook.after(ctx, command, CommandOutcome::Rejected).await;
+                }
+                return true;
+            }
+        }
+
+        (info.executor)(ctx, command, wrapper, config, db, jobs, cluster_client).await;
+
+        for hook in &self.hooks {
+            hook.after(ctx, command, CommandOutcome::Executed).await;
+        }
+
+        true
+    }
+}
+
+/// Send an ephemeral rejection for a `before` hook that short-circuited
+/// dispatch, before the command handler has sent any response of its own.
+async fn reject(ctx: &Context, command: &CommandInteraction, reason: &str) {
+    let response = CreateInteractionResponseMessage::new()
+        .content(format!("⛔ {}", reason))
+        .ephemeral(true);
+    if let Err(e) = command
+        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+        .await
+    {
+        error!("Failed to send hook rejection: {}", e);
+    }
+}
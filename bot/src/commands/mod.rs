@@ -1,48 +1,117 @@
 //! Discord slash commands module.
 
 mod approve;
+mod cluster;
+mod hooks;
+mod manager;
 mod project;
 mod register;
 mod share;
 mod status;
 mod task;
+mod tasks;
 
-pub use approve::approve;
-pub use project::project;
-pub use register::handle_register;
+pub use approve::{approve, build_approval_components, handle_component, handle_modal_submit};
+pub use manager::CommandManager;
+pub use project::{autocomplete as project_autocomplete, project};
+pub use register::{auth_token_for, handle_register};
 pub use share::share;
 pub use status::status;
-pub use task::task;
-
-use serenity::all::{Command, Context, GuildId, Ready};
-use tracing::{error, info};
-
-/// Register all slash commands with Discord.
-pub async fn register_commands(ctx: &Context, ready: &Ready, guild_id: Option<u64>) {
-    info!("Registering slash commands...");
-
-    let commands = vec![
-        task::register(),
-        status::register(),
-        approve::register(),
-        project::register(),
-        register::register(),
-        share::register(),
-    ];
-
-    // Register to specific guild (faster) or globally
-    if let Some(gid) = guild_id {
-        let guild = GuildId::new(gid);
-        match guild.set_commands(&ctx.http, commands).await {
-            Ok(cmds) => info!("Registered {} guild commands", cmds.len()),
-            Err(e) => error!("Failed to register guild commands: {}", e),
-        }
-    } else {
-        match Command::set_global_commands(&ctx.http, commands).await {
-            Ok(cmds) => info!("Registered {} global commands", cmds.len()),
-            Err(e) => error!("Failed to register global commands: {}", e),
-        }
+pub use task::{inflight_tasks, is_owner as task_is_owner, task};
+pub use tasks::tasks;
+
+use anyhow::Result;
+use serenity::all::{
+    CommandInteraction, Context, CreateAttachment, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+use tracing::error;
+
+use crate::config::Config;
+
+/// Above this many chars, `/status` stops splitting output across follow-up
+/// Discord messages and `/task` stops truncating it inline; both instead
+/// attach it as a file, keeping only a short summary in the visible message.
+pub const OUTPUT_ATTACHMENT_THRESHOLD: usize = 4000;
+
+/// How much of the output to show inline as a preview when it's long enough
+/// to be attached as a file instead.
+const ATTACHMENT_PREVIEW_CHARS: usize = 300;
+
+/// Build a `.txt` attachment carrying the full output of `task_id`.
+pub fn output_attachment(task_id: &str, output: &str) -> CreateAttachment {
+    CreateAttachment::bytes(output.as_bytes().to_vec(), format!("task-{}-output.txt", task_id))
+}
+
+/// A short inline preview of `output`, for use alongside `output_attachment`.
+pub fn output_preview(output: &str) -> &str {
+    truncate_chars(output, ATTACHMENT_PREVIEW_CHARS)
+}
+
+/// Truncate `s` to at most `max_chars` chars, on a UTF-8 character boundary,
+/// instead of the raw byte slicing (`&s[..n]`) that panics or corrupts
+/// multibyte output.
+pub fn truncate_chars(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        Some((idx, _)) => &s[..idx],
+        None => s,
+    }
+}
+
+/// Split `s` into chunks of at most `max_chars` chars each, on character
+/// boundaries.
+pub fn split_into_chunks(s: &str, max_chars: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = s;
+    while !rest.is_empty() {
+        let split_at = rest.char_indices().nth(max_chars).map(|(idx, _)| idx).unwrap_or(rest.len());
+        let (chunk, remainder) = rest.split_at(split_at);
+        chunks.push(chunk);
+        rest = remainder;
     }
+    chunks
+}
+
+/// Send a deferred response immediately, buying the handler up to 15 minutes
+/// to call into the wrapper service before editing the response in, instead
+/// of racing Discord's 3-second initial-response window.
+pub async fn defer(ctx: &Context, command: &CommandInteraction, ephemeral: bool) -> Result<()> {
+    let response = CreateInteractionResponseMessage::new().ephemeral(ephemeral);
+    command
+        .create_response(&ctx.http, CreateInteractionResponse::Defer(response))
+        .await
+        .map_err(|e| {
+            error!("Failed to defer interaction response: {}", e);
+            e.into()
+        })
+}
+
+/// Whether the invoking member is allowed to register or share wrappers:
+/// holds `ADMINISTRATOR`, holds one of `config.allowed_role_ids`, or no
+/// allowlist is configured at all (the unrestricted default).
+pub fn member_is_allowed(command: &CommandInteraction, config: &Config) -> bool {
+    let Some(member) = &command.member else {
+        return config.allowed_role_ids.is_empty();
+    };
+
+    if member.permissions.is_some_and(|p| p.administrator()) {
+        return true;
+    }
+
+    if config.allowed_role_ids.is_empty() {
+        return true;
+    }
+
+    member.roles.iter().any(|r| config.allowed_role_ids.contains(&r.get()))
+}
 
-    info!("{} is connected!", ready.user.name);
+/// Reply with an ephemeral permission-denied message for a non-deferred
+/// command response.
+pub async fn reject_unauthorized(ctx: &Context, command: &CommandInteraction) {
+    let response = CreateInteractionResponseMessage::new()
+        .content("❌ You don't have permission to do that. Ask a server admin for the right role.")
+        .ephemeral(true);
+    let _ = command
+        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+        .await;
 }
@@ -1,12 +1,15 @@
 //! /project command - Manage registered projects (per-user isolated).
 
 use serenity::all::{
-    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
-    CreateInteractionResponse, CreateInteractionResponseMessage,
+    AutocompleteChoice, CommandInteraction, CommandOptionType, Context, CreateAutocompleteResponse,
+    CreateCommand, CreateCommandOption, CreateInteractionResponse, CreateInteractionResponseFollowup,
+    CreateInteractionResponseMessage,
 };
 use tracing::{error, info};
 
 use crate::client::{ProjectRequest, WrapperClient};
+use crate::commands::{member_is_allowed, reject_unauthorized};
+use crate::config::Config;
 
 /// Create the command registration.
 pub fn register() -> CreateCommand {
@@ -62,13 +65,83 @@ pub fn register() -> CreateCommand {
                     "name",
                     "Project name to remove",
                 )
+                .required(true)
+                .set_autocomplete(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "share",
+                "Grant another user access to run /task against this project",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "name",
+                    "Project name to share",
+                )
+                .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "user",
+                    "The user to grant access to",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "transfer",
+                "Hand off ownership of a project to another user",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "name",
+                    "Project name to transfer",
+                )
+                .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "user",
+                    "The user to become the new owner",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "history",
+                "Show recent activity for a project",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "name",
+                    "Project name",
+                )
                 .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "limit",
+                    "Number of entries to show (default 20)",
+                )
+                .required(false),
             ),
         )
 }
 
 /// Handle the /project command.
-pub async fn project(ctx: &Context, command: &CommandInteraction, wrapper: &WrapperClient) {
+pub async fn project(ctx: &Context, command: &CommandInteraction, wrapper: &WrapperClient, config: &Config) {
     // Get user ID from Discord (server-side, cannot be spoofed)
     let user_id = command.user.id.to_string();
 
@@ -85,13 +158,23 @@ pub async fn project(ctx: &Context, command: &CommandInteraction, wrapper: &Wrap
         subcommand, user_id
     );
 
+    // Adding, removing, sharing, and transferring projects is gated; listing
+    // what's already there is not.
+    if matches!(subcommand, "add" | "remove" | "share" | "transfer") && !member_is_allowed(command, config) {
+        reject_unauthorized(ctx, command).await;
+        return;
+    }
+
     match subcommand {
         "list" => handle_list(ctx, command, wrapper, &user_id).await,
         "add" => handle_add(ctx, command, wrapper, &user_id).await,
         "remove" => handle_remove(ctx, command, wrapper, &user_id).await,
+        "share" => handle_share(ctx, command, wrapper, &user_id).await,
+        "transfer" => handle_transfer(ctx, command, wrapper, &user_id).await,
+        "history" => handle_history(ctx, command, wrapper, &user_id).await,
         _ => {
             let response = CreateInteractionResponseMessage::new()
-                .content("Unknown subcommand. Use `/project list`, `/project add`, or `/project remove`.")
+                .content("Unknown subcommand. Use `/project list`, `/project add`, `/project remove`, `/project share`, `/project transfer`, or `/project history`.")
                 .ephemeral(true);
             let _ = command
                 .create_response(&ctx.http, CreateInteractionResponse::Message(response))
@@ -108,29 +191,69 @@ async fn handle_list(
 ) {
     match wrapper.list_projects(user_id).await {
         Ok(projects) => {
-            let content = if projects.is_empty() {
-                "**Your Projects:**\n\nNo projects registered.\n\nUse `/project add name:<name> path:<path>` to add one.".to_string()
+            let (owned, shared): (Vec<_>, Vec<_>) =
+                projects.into_iter().partition(|p| p.owner_id == user_id);
+
+            let mut lines = vec!["**Your Projects:**\n".to_string()];
+            if owned.is_empty() && shared.is_empty() {
+                lines.push("No projects registered.\n\nUse `/project add name:<name> path:<path>` to add one.".to_string());
             } else {
-                let mut lines = vec!["**Your Projects:**\n".to_string()];
-                for p in projects {
-                    let desc = if p.description.is_empty() {
-                        String::new()
-                    } else {
-                        format!(" - {}", p.description)
-                    };
-                    lines.push(format!("`{}` → `{}`{}", p.name, p.path, desc));
+                if owned.is_empty() {
+                    lines.push("_None._".to_string());
+                } else {
+                    for p in &owned {
+                        let desc = if p.description.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" - {}", p.description)
+                        };
+                        lines.push(format!("`{}` → `{}`{}", p.name, p.path, desc));
+                    }
                 }
-                lines.join("\n")
+
+                if !shared.is_empty() {
+                    lines.push("\n**Shared With You:**\n".to_string());
+                    for p in &shared {
+                        let desc = if p.description.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" - {}", p.description)
+                        };
+                        lines.push(format!(
+                            "`{}` → `{}`{} (owned by <@{}>)",
+                            p.name, p.path, desc, p.owner_id
+                        ));
+                    }
+                }
+            }
+
+            // A user with a long project history (or long descriptions) can
+            // blow past Discord's 2000-character message limit, so the list
+            // is chunked at line boundaries: the first chunk is the initial
+            // response, the rest go out as follow-ups.
+            let chunks = chunk_lines(&lines, 1900);
+            let Some((first, rest)) = chunks.split_first() else {
+                return;
             };
 
             let response = CreateInteractionResponseMessage::new()
-                .content(content)
+                .content(first.clone())
                 .ephemeral(false);
             if let Err(e) = command
                 .create_response(&ctx.http, CreateInteractionResponse::Message(response))
                 .await
             {
                 error!("Failed to send project list: {}", e);
+                return;
+            }
+
+            for chunk in rest {
+                let followup = CreateInteractionResponseFollowup::new()
+                    .content(chunk.clone())
+                    .ephemeral(false);
+                if let Err(e) = command.create_followup(&ctx.http, followup).await {
+                    error!("Failed to send project list follow-up: {}", e);
+                }
             }
         }
         Err(e) => {
@@ -145,6 +268,30 @@ async fn handle_list(
     }
 }
 
+/// Join `lines` into as few messages as possible, each under `limit`
+/// characters, without splitting a line across messages.
+fn chunk_lines(lines: &[String], limit: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for line in lines {
+        let separator = if current.is_empty() { 0 } else { 1 };
+        if !current.is_empty() && current.len() + separator + line.len() > limit {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
 async fn handle_add(
     ctx: &Context,
     command: &CommandInteraction,
@@ -203,7 +350,7 @@ async fn handle_add(
     match wrapper.add_project(request).await {
         Ok(project) => {
             let content = format!(
-                "✅ **Project Added**\n\n**Name:** `{}`\n**Path:** `{}`\n\nUse `/task prompt:\"...\" project:{}` to work on this project.",
+                "✅ **Project Added**\n\n**Name:** `{}`\n**Path:** `{}`\n\nUse `/task run prompt:\"...\" project:{}` to work on this project.",
                 project.name, project.path, project.name
             );
             let response = CreateInteractionResponseMessage::new()
@@ -288,3 +435,327 @@ async fn handle_remove(
         }
     }
 }
+
+async fn handle_share(
+    ctx: &Context,
+    command: &CommandInteraction,
+    wrapper: &WrapperClient,
+    user_id: &str,
+) {
+    // Extract subcommand options using pattern matching for Serenity 0.12
+    let sub_opts = command
+        .data
+        .options
+        .first()
+        .and_then(|opt| {
+            if let serenity::all::CommandDataOptionValue::SubCommand(opts) = &opt.value {
+                Some(opts.clone())
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    let name = sub_opts
+        .iter()
+        .find(|o| o.name == "name")
+        .and_then(|o| o.value.as_str())
+        .unwrap_or("");
+
+    let target_user_id = sub_opts
+        .iter()
+        .find(|o| o.name == "user")
+        .and_then(|o| o.value.as_user_id());
+
+    let Some(target_id_value) = target_user_id else {
+        let response = CreateInteractionResponseMessage::new()
+            .content("Please specify a user to share with.")
+            .ephemeral(true);
+        let _ = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await;
+        return;
+    };
+
+    let target_user = command.data.resolved.users.get(&target_id_value);
+    let target_id = target_id_value.to_string();
+    let target_name = target_user.map(|u| u.name.clone()).unwrap_or_else(|| target_id.clone());
+
+    if name.is_empty() {
+        let response = CreateInteractionResponseMessage::new()
+            .content("❌ Project `name` is required.")
+            .ephemeral(true);
+        let _ = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await;
+        return;
+    }
+
+    if target_id == user_id {
+        let response = CreateInteractionResponseMessage::new()
+            .content("You already have access to your own project!")
+            .ephemeral(true);
+        let _ = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await;
+        return;
+    }
+
+    match wrapper.share_project(user_id, name, &target_id).await {
+        Ok(project) => {
+            let content = format!(
+                "✅ **Project Shared**\n\n<@{}> (`{}`) can now run `/task` against `{}`.\n\n**Currently shared with:** {} user(s)",
+                target_id,
+                target_name,
+                project.name,
+                project.authorized_ids.len(),
+            );
+            let response = CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(false);
+            if let Err(e) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                .await
+            {
+                error!("Failed to send project share confirmation: {}", e);
+            }
+        }
+        Err(e) => {
+            error!("Failed to share project: {}", e);
+            let response = CreateInteractionResponseMessage::new()
+                .content(format!("❌ Failed to share project: {}", e))
+                .ephemeral(true);
+            let _ = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                .await;
+        }
+    }
+}
+
+async fn handle_transfer(
+    ctx: &Context,
+    command: &CommandInteraction,
+    wrapper: &WrapperClient,
+    user_id: &str,
+) {
+    // Extract subcommand options using pattern matching for Serenity 0.12
+    let sub_opts = command
+        .data
+        .options
+        .first()
+        .and_then(|opt| {
+            if let serenity::all::CommandDataOptionValue::SubCommand(opts) = &opt.value {
+                Some(opts.clone())
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    let name = sub_opts
+        .iter()
+        .find(|o| o.name == "name")
+        .and_then(|o| o.value.as_str())
+        .unwrap_or("");
+
+    let target_user_id = sub_opts
+        .iter()
+        .find(|o| o.name == "user")
+        .and_then(|o| o.value.as_user_id());
+
+    let Some(target_id_value) = target_user_id else {
+        let response = CreateInteractionResponseMessage::new()
+            .content("Please specify a user to transfer ownership to.")
+            .ephemeral(true);
+        let _ = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await;
+        return;
+    };
+
+    let target_user = command.data.resolved.users.get(&target_id_value);
+    let target_id = target_id_value.to_string();
+    let target_name = target_user.map(|u| u.name.clone()).unwrap_or_else(|| target_id.clone());
+
+    if name.is_empty() {
+        let response = CreateInteractionResponseMessage::new()
+            .content("❌ Project `name` is required.")
+            .ephemeral(true);
+        let _ = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await;
+        return;
+    }
+
+    if target_id == user_id {
+        let response = CreateInteractionResponseMessage::new()
+            .content("You already own this project!")
+            .ephemeral(true);
+        let _ = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await;
+        return;
+    }
+
+    match wrapper.transfer_project(user_id, name, &target_id).await {
+        Ok(project) => {
+            let content = format!(
+                "✅ **Project Ownership Transferred**\n\n<@{}> (`{}`) is now the owner of `{}`, keeping its existing share list.",
+                target_id, target_name, project.name,
+            );
+            let response = CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(false);
+            if let Err(e) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                .await
+            {
+                error!("Failed to send project transfer confirmation: {}", e);
+            }
+        }
+        Err(e) => {
+            error!("Failed to transfer project ownership: {}", e);
+            let response = CreateInteractionResponseMessage::new()
+                .content(format!("❌ Failed to transfer ownership: {}", e))
+                .ephemeral(true);
+            let _ = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                .await;
+        }
+    }
+}
+
+const DEFAULT_HISTORY_LIMIT: i64 = 20;
+
+async fn handle_history(
+    ctx: &Context,
+    command: &CommandInteraction,
+    wrapper: &WrapperClient,
+    user_id: &str,
+) {
+    // Extract subcommand options using pattern matching for Serenity 0.12
+    let sub_opts = command
+        .data
+        .options
+        .first()
+        .and_then(|opt| {
+            if let serenity::all::CommandDataOptionValue::SubCommand(opts) = &opt.value {
+                Some(opts.clone())
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    let name = sub_opts
+        .iter()
+        .find(|o| o.name == "name")
+        .and_then(|o| o.value.as_str())
+        .unwrap_or("");
+
+    let limit = sub_opts
+        .iter()
+        .find(|o| o.name == "limit")
+        .and_then(|o| o.value.as_i64())
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .clamp(1, 200) as u32;
+
+    if name.is_empty() {
+        let response = CreateInteractionResponseMessage::new()
+            .content("❌ Project `name` is required.")
+            .ephemeral(true);
+        let _ = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await;
+        return;
+    }
+
+    match wrapper.project_history(user_id, name, limit).await {
+        Ok(history) => {
+            let content = if history.entries.is_empty() {
+                format!("**History for `{}`**\n\nNo activity recorded yet.", name)
+            } else {
+                let mut lines = vec![format!("**History for `{}`** (newest first)\n", name)];
+                for entry in &history.entries {
+                    let detail = entry
+                        .detail
+                        .as_ref()
+                        .map(|d| format!(" - {}", d))
+                        .unwrap_or_default();
+                    lines.push(format!(
+                        "`{}` **{}** by <@{}>{}",
+                        entry.timestamp, entry.action, entry.actor_id, detail
+                    ));
+                }
+                lines.join("\n")
+            };
+
+            let response = CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(false);
+            if let Err(e) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                .await
+            {
+                error!("Failed to send project history: {}", e);
+            }
+        }
+        Err(e) => {
+            error!("Failed to fetch project history: {}", e);
+            let response = CreateInteractionResponseMessage::new()
+                .content(format!("❌ Failed to fetch history: {}", e))
+                .ephemeral(true);
+            let _ = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                .await;
+        }
+    }
+}
+
+/// Respond to autocomplete requests for project-name fields — `/project
+/// remove`'s `name` and `/task run`'s `project` both point here — suggesting
+/// names the user owns or has shared access to that match the partial input.
+pub async fn autocomplete(ctx: &Context, interaction: &CommandInteraction, wrapper: &WrapperClient) {
+    let user_id = interaction.user.id.to_string();
+
+    let partial = interaction
+        .data
+        .options
+        .first()
+        .and_then(|opt| {
+            if let serenity::all::CommandDataOptionValue::SubCommand(opts) = &opt.value {
+                opts.iter().find_map(|o| match &o.value {
+                    serenity::all::CommandDataOptionValue::Autocomplete { value, .. } => {
+                        Some(value.clone())
+                    }
+                    _ => None,
+                })
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let choices: Vec<AutocompleteChoice> = match wrapper.list_projects(&user_id).await {
+        Ok(projects) => projects
+            .into_iter()
+            .map(|p| p.name)
+            .filter(|name| name.to_lowercase().contains(&partial))
+            .take(25)
+            .map(|name| AutocompleteChoice::new(name.clone(), name))
+            .collect(),
+        Err(e) => {
+            error!("Failed to fetch projects for autocomplete: {}", e);
+            Vec::new()
+        }
+    };
+
+    let response = CreateAutocompleteResponse::new().set_choices(choices);
+    if let Err(e) = interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response))
+        .await
+    {
+        error!("Failed to send autocomplete response: {}", e);
+    }
+}
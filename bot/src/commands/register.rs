@@ -1,12 +1,41 @@
 //! /register command - Register local wrapper or manage user settings.
 
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
 use serenity::all::{
     CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
-    CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse,
 };
 use tracing::{error, info};
 
 use crate::client::{ExecutionMode, RegisterLocalRequest, WrapperClient};
+use crate::commands::{member_is_allowed, reject_unauthorized};
+use crate::config::Config;
+use crate::jobs::JobStore;
+
+/// How many recent jobs `/register status` shows.
+const RECENT_JOBS_LIMIT: i64 = 5;
+
+/// Raw auth tokens for users who registered one, keyed by Discord ID. Kept
+/// only in memory - never persisted - so the bot can attach a token as
+/// `Authorization: Bearer` on subsequent task dispatches without storing the
+/// secret anywhere durable. Lost on restart; re-run `/register local` or
+/// `/register rotate-token` to re-establish one.
+static AUTH_TOKENS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+fn auth_tokens() -> &'static Mutex<HashMap<String, String>> {
+    AUTH_TOKENS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The raw auth token `user_id` registered, if any.
+pub fn auth_token_for(user_id: &str) -> Option<String> {
+    auth_tokens().lock().unwrap().get(user_id).cloned()
+}
+
+fn store_auth_token(user_id: &str, token: String) {
+    auth_tokens().lock().unwrap().insert(user_id.to_string(), token);
+}
 
 /// Create the command registration.
 pub fn register() -> CreateCommand {
@@ -25,6 +54,14 @@ pub fn register() -> CreateCommand {
                     "Your wrapper URL (e.g., http://your-ip:8000)",
                 )
                 .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "token",
+                    "A bearer token your wrapper requires - only its hash is ever stored",
+                )
+                .required(false),
             ),
         )
         .add_option(
@@ -34,6 +71,28 @@ pub fn register() -> CreateCommand {
                 "Unregister your local wrapper",
             ),
         )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "rotate-token",
+                "Generate a fresh auth token, invalidating the old one",
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "cluster",
+                "Pin your cluster-mode tasks to a specific node",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "node",
+                    "The cluster node id to pin your tasks to",
+                )
+                .required(true),
+            ),
+        )
         .add_option(
             CreateCommandOption::new(
                 CommandOptionType::SubCommand,
@@ -61,7 +120,13 @@ pub fn register() -> CreateCommand {
 }
 
 /// Handle the /register command.
-pub async fn handle_register(ctx: &Context, command: &CommandInteraction, wrapper: &WrapperClient) {
+pub async fn handle_register(
+    ctx: &Context,
+    command: &CommandInteraction,
+    wrapper: &WrapperClient,
+    config: &Config,
+    jobs: &JobStore,
+) {
     let subcommand = command
         .data
         .options
@@ -77,11 +142,22 @@ pub async fn handle_register(ctx: &Context, command: &CommandInteraction, wrappe
         subcommand, user_id
     );
 
+    // Spinning up a compute-backed wrapper is gated to allowed roles/admins;
+    // checking status of your own registration is not.
+    if (subcommand == "local" || subcommand == "rotate-token" || subcommand == "cluster")
+        && !member_is_allowed(command, config)
+    {
+        reject_unauthorized(ctx, command).await;
+        return;
+    }
+
     match subcommand {
         "local" => handle_register_local(ctx, command, wrapper, &user_id, &user_name).await,
         "unregister" => handle_unregister(ctx, command, wrapper, &user_id).await,
         "mode" => handle_set_mode(ctx, command, wrapper, &user_id).await,
-        "status" => handle_status(ctx, command, wrapper, &user_id).await,
+        "status" => handle_status(ctx, command, wrapper, jobs, &user_id).await,
+        "rotate-token" => handle_rotate_token(ctx, command, wrapper, &user_id).await,
+        "cluster" => handle_set_cluster_node(ctx, command, wrapper, &user_id).await,
         _ => {
             let response = CreateInteractionResponseMessage::new()
                 .content("Unknown subcommand.")
@@ -130,41 +206,102 @@ async fn handle_register_local(
         return;
     }
 
+    // Defer immediately so the reachability probe in `register_local` can
+    // take its full timeout budget without blowing past Discord's 3-second
+    // initial-response window.
+    if crate::commands::defer(ctx, command, false).await.is_err() {
+        return;
+    }
+
+    let token = sub_opts
+        .iter()
+        .find(|o| o.name == "token")
+        .and_then(|o| o.value.as_str())
+        .map(|s| s.to_string());
+
     let request = RegisterLocalRequest {
         discord_id: user_id.to_string(),
         discord_name: user_name.to_string(),
         wrapper_url: url.to_string(),
-        auth_token: None,
+        auth_token: token.clone(),
+        capabilities: None,
     };
 
     match wrapper.register_local(request).await {
         Ok(user) => {
+            if let Some(token) = token {
+                store_auth_token(user_id, token);
+            }
+
+            let auth_note = if user.auth_token_set {
+                "\n**Auth:** 🔒 a bearer token is required and will be sent automatically"
+            } else {
+                ""
+            };
+            let capabilities_note = match &user.capabilities {
+                Some(caps) => format!(
+                    "\n\n**Capabilities:**\n\
+                    Runtimes: {}\n\
+                    Max concurrency: {}\n\
+                    Endpoint: `{}`",
+                    caps.runtimes.join(", "),
+                    caps.max_concurrency,
+                    caps.resource_path,
+                ),
+                None => String::new(),
+            };
             let content = format!(
                 "✅ **Local Wrapper Registered**\n\n\
                 **URL:** `{}`\n\
-                **Default Mode:** {}\n\n\
-                Now run the wrapper on your machine:\n\
-                ```bash\n\
-                cd wrapper && uvicorn wrapper.main:app --host 0.0.0.0 --port 8000\n\
-                ```\n\n\
-                Then use `/task prompt:\"...\" project:my-project` to run tasks!",
+                **Default Mode:** {}{}{}\n\n\
+                Use `/task run prompt:\"...\" project:my-project` to run tasks!",
                 user.local_wrapper_url.unwrap_or_default(),
                 user.default_mode,
+                auth_note,
+                capabilities_note,
+            );
+            let edit = EditInteractionResponse::new().content(content);
+            if let Err(e) = command.edit_response(&ctx.http, edit).await {
+                error!("Failed to edit register response: {}", e);
+            }
+        }
+        Err(e) => {
+            error!("Failed to register local wrapper: {}", e);
+            let edit = EditInteractionResponse::new().content(format!("❌ Failed to register: {}", e));
+            if let Err(e) = command.edit_response(&ctx.http, edit).await {
+                error!("Failed to edit register error response: {}", e);
+            }
+        }
+    }
+}
+
+/// Handle `/register rotate-token`: generate a fresh token, store its hash
+/// on the wrapper, and show the new raw value to the user once (ephemeral -
+/// it can't be recovered afterward, only rotated again).
+async fn handle_rotate_token(ctx: &Context, command: &CommandInteraction, wrapper: &WrapperClient, user_id: &str) {
+    match wrapper.rotate_token(user_id).await {
+        Ok(token) => {
+            store_auth_token(user_id, token.clone());
+            let content = format!(
+                "✅ **Auth Token Rotated**\n\n\
+                New token (shown once, store it somewhere safe):\n\
+                ```\n{}\n```",
+                token,
             );
             let response = CreateInteractionResponseMessage::new()
                 .content(content)
-                .ephemeral(false);
+                .ephemeral(true);
             if let Err(e) = command
                 .create_response(&ctx.http, CreateInteractionResponse::Message(response))
                 .await
             {
-                error!("Failed to send register response: {}", e);
+                error!("Failed to send rotate-token response: {}", e);
             }
         }
         Err(e) => {
-            error!("Failed to register local wrapper: {}", e);
+            error!("Failed to rotate auth token: {}", e);
             let response = CreateInteractionResponseMessage::new()
-                .content(format!("❌ Failed to register: {}", e))
+                .content(format!("❌ Failed to rotate token: {}", e))
                 .ephemeral(true);
             let _ = command
                 .create_response(&ctx.http, CreateInteractionResponse::Message(response))
@@ -173,6 +310,64 @@ async fn handle_register_local(
     }
 }
 
+/// Handle `/register cluster node:<id>`: pin the caller's cluster-mode
+/// tasks to a specific node instead of the orchestrator's default
+/// allocation.
+async fn handle_set_cluster_node(ctx: &Context, command: &CommandInteraction, wrapper: &WrapperClient, user_id: &str) {
+    let sub_opts = command
+        .data
+        .options
+        .first()
+        .and_then(|opt| {
+            if let serenity::all::CommandDataOptionValue::SubCommand(opts) = &opt.value {
+                Some(opts.clone())
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    let node_id = sub_opts
+        .iter()
+        .find(|o| o.name == "node")
+        .and_then(|o| o.value.as_str())
+        .unwrap_or("");
+
+    if node_id.is_empty() {
+        let response = CreateInteractionResponseMessage::new()
+            .content("❌ A node id is required.")
+            .ephemeral(true);
+        let _ = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await;
+        return;
+    }
+
+    if crate::commands::defer(ctx, command, false).await.is_err() {
+        return;
+    }
+
+    match wrapper.set_cluster_node(user_id, node_id).await {
+        Ok(user) => {
+            let content = format!(
+                "✅ Cluster tasks pinned to node `{}`",
+                user.cluster_node_id.unwrap_or_else(|| node_id.to_string()),
+            );
+            let edit = EditInteractionResponse::new().content(content);
+            if let Err(e) = command.edit_response(&ctx.http, edit).await {
+                error!("Failed to edit cluster node response: {}", e);
+            }
+        }
+        Err(e) => {
+            error!("Failed to set cluster node: {}", e);
+            let edit = EditInteractionResponse::new().content(format!("❌ Failed to pin cluster node: {}", e));
+            if let Err(e) = command.edit_response(&ctx.http, edit).await {
+                error!("Failed to edit cluster node error response: {}", e);
+            }
+        }
+    }
+}
+
 async fn handle_unregister(
     ctx: &Context,
     command: &CommandInteraction,
@@ -234,6 +429,12 @@ async fn handle_set_mode(
         _ => ExecutionMode::Local,
     };
 
+    // Defer immediately so a slow or unreachable wrapper can't blow past
+    // Discord's 3-second initial-response window.
+    if crate::commands::defer(ctx, command, false).await.is_err() {
+        return;
+    }
+
     match wrapper.set_user_mode(user_id, mode).await {
         Ok(user) => {
             let content = format!(
@@ -246,24 +447,20 @@ async fn handle_set_mode(
                     "your local machine"
                 }
             );
-            let response = CreateInteractionResponseMessage::new()
-                .content(content)
-                .ephemeral(false);
-            if let Err(e) = command
-                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
-                .await
-            {
-                error!("Failed to send mode response: {}", e);
+            let edit = EditInteractionResponse::new().content(content);
+            if let Err(e) = command.edit_response(&ctx.http, edit).await {
+                error!("Failed to edit mode response: {}", e);
             }
         }
         Err(e) => {
             error!("Failed to set mode: {}", e);
-            let response = CreateInteractionResponseMessage::new()
-                .content(format!("❌ Failed to set mode: {}\n\nYou may need to register first with `/register local url:<your-url>`", e))
-                .ephemeral(true);
-            let _ = command
-                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
-                .await;
+            let edit = EditInteractionResponse::new().content(format!(
+                "❌ Failed to set mode: {}\n\nYou may need to register first with `/register local url:<your-url>`",
+                e
+            ));
+            if let Err(e) = command.edit_response(&ctx.http, edit).await {
+                error!("Failed to edit mode error response: {}", e);
+            }
         }
     }
 }
@@ -272,6 +469,7 @@ async fn handle_status(
     ctx: &Context,
     command: &CommandInteraction,
     wrapper: &WrapperClient,
+    jobs: &JobStore,
     user_id: &str,
 ) {
     match wrapper.get_user(user_id).await {
@@ -291,18 +489,61 @@ async fn handle_status(
                 "❌ Not enabled".to_string()
             };
 
+            // List cluster nodes and their health when the user actually has
+            // cluster access - listing nodes nobody can use just adds noise.
+            let nodes_section = if user.cluster_enabled {
+                match wrapper.list_nodes().await {
+                    Ok(nodes) if !nodes.is_empty() => {
+                        let lines: Vec<String> = nodes
+                            .iter()
+                            .map(|n| {
+                                let health = if n.healthy { "✅" } else { "❌" };
+                                let pinned = if user.cluster_node_id.as_deref() == Some(n.node_id.as_str()) {
+                                    " (pinned)"
+                                } else {
+                                    ""
+                                };
+                                format!("{} `{}`{}", health, n.node_id, pinned)
+                            })
+                            .collect();
+                        format!("\n**Cluster Nodes:**\n{}", lines.join("\n"))
+                    }
+                    Ok(_) => "\n**Cluster Nodes:** none configured".to_string(),
+                    Err(e) => format!("\n**Cluster Nodes:** failed to list ({})", e),
+                }
+            } else {
+                String::new()
+            };
+
+            let jobs_section = match jobs.recent_for_user(user_id, RECENT_JOBS_LIMIT).await {
+                Ok(records) if records.is_empty() => "\n**Recent Jobs:** none yet".to_string(),
+                Ok(records) => {
+                    let lines: Vec<String> = records
+                        .iter()
+                        .map(|j| {
+                            let target = j.target.as_ref().map(|t| format!(" on `{}`", t)).unwrap_or_default();
+                            format!("`{}` - **{}**{}", j.job_id, j.state, target)
+                        })
+                        .collect();
+                    format!("\n**Recent Jobs:**\n{}", lines.join("\n"))
+                }
+                Err(e) => format!("\n**Recent Jobs:** failed to load ({})", e),
+            };
+
             let content = format!(
                 "**Your Registration Status**\n\n\
                 **Discord ID:** `{}`\n\
                 **Local Wrapper:** {}\n\
                 **Cluster Access:** {}\n\
                 **Default Mode:** `{}`\n\
-                **Last Seen:** {}",
+                **Last Seen:** {}{}{}",
                 user.discord_id,
                 local_status,
                 cluster_status,
                 user.default_mode,
                 user.last_seen,
+                nodes_section,
+                jobs_section,
             );
             let response = CreateInteractionResponseMessage::new()
                 .content(content)
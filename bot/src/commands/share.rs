@@ -7,6 +7,8 @@ use serenity::all::{
 use tracing::{error, info};
 
 use crate::client::WrapperClient;
+use crate::commands::{member_is_allowed, reject_unauthorized};
+use crate::config::Config;
 
 /// Create the command registration.
 pub fn register() -> CreateCommand {
@@ -56,10 +58,25 @@ pub fn register() -> CreateCommand {
                 "List wrappers you have access to (your own + shared with you)",
             ),
         )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "transfer",
+                "Hand off ownership of your wrapper to another user",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "user",
+                    "The user to become the new owner",
+                )
+                .required(true),
+            ),
+        )
 }
 
 /// Handle the /share command.
-pub async fn share(ctx: &Context, command: &CommandInteraction, wrapper: &WrapperClient) {
+pub async fn share(ctx: &Context, command: &CommandInteraction, wrapper: &WrapperClient, config: &Config) {
     // Get user ID from Discord (server-side, cannot be spoofed)
     let user_id = command.user.id.to_string();
 
@@ -76,14 +93,22 @@ pub async fn share(ctx: &Context, command: &CommandInteraction, wrapper: &Wrappe
         subcommand, user_id
     );
 
+    // Granting/revoking/transferring access to a wrapper is gated; listing
+    // what exists is not.
+    if matches!(subcommand, "add" | "remove" | "transfer") && !member_is_allowed(command, config) {
+        reject_unauthorized(ctx, command).await;
+        return;
+    }
+
     match subcommand {
         "add" => handle_add(ctx, command, wrapper, &user_id).await,
         "remove" => handle_remove(ctx, command, wrapper, &user_id).await,
         "list" => handle_list(ctx, command, wrapper, &user_id).await,
         "available" => handle_available(ctx, command, wrapper, &user_id).await,
+        "transfer" => handle_transfer(ctx, command, wrapper, &user_id).await,
         _ => {
             let response = CreateInteractionResponseMessage::new()
-                .content("Unknown subcommand. Use `/share add`, `/share remove`, `/share list`, or `/share available`.")
+                .content("Unknown subcommand. Use `/share add`, `/share remove`, `/share list`, `/share available`, or `/share transfer`.")
                 .ephemeral(true);
             let _ = command
                 .create_response(&ctx.http, CreateInteractionResponse::Message(response))
@@ -147,7 +172,7 @@ async fn handle_add(
     match wrapper.share_with(user_id, &target_id).await {
         Ok(result) => {
             let content = format!(
-                "**Wrapper Shared**\n\n<@{}> (`{}`) now has access to your wrapper.\n\nThey can use it with:\n`/task prompt:\"...\" target:@{}`\n\n**Currently shared with:** {} user(s)",
+                "**Wrapper Shared**\n\n<@{}> (`{}`) now has access to your wrapper.\n\nThey can use it with:\n`/task run prompt:\"...\" target:@{}`\n\n**Currently shared with:** {} user(s)",
                 target_id,
                 target_name,
                 command.user.name,
@@ -317,7 +342,7 @@ async fn handle_available(
                     lines.push(label);
                 }
                 lines.push("\nTo use someone else's wrapper:".to_string());
-                lines.push("`/task prompt:\"...\" target:@username`".to_string());
+                lines.push("`/task run prompt:\"...\" target:@username`".to_string());
                 lines.join("\n")
             };
 
@@ -342,3 +367,80 @@ async fn handle_available(
         }
     }
 }
+
+async fn handle_transfer(
+    ctx: &Context,
+    command: &CommandInteraction,
+    wrapper: &WrapperClient,
+    user_id: &str,
+) {
+    // Extract target user from subcommand options using pattern matching for Serenity 0.12
+    let sub_opts = command
+        .data
+        .options
+        .first()
+        .and_then(|opt| {
+            if let serenity::all::CommandDataOptionValue::SubCommand(opts) = &opt.value {
+                Some(opts.clone())
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    let target_user_id = sub_opts
+        .iter()
+        .find(|o| o.name == "user")
+        .and_then(|o| o.value.as_user_id());
+
+    let Some(target_id_value) = target_user_id else {
+        let response = CreateInteractionResponseMessage::new()
+            .content("Please specify a user to transfer ownership to.")
+            .ephemeral(true);
+        let _ = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await;
+        return;
+    };
+
+    let target_user = command.data.resolved.users.get(&target_id_value);
+    let target_id = target_id_value.to_string();
+    let target_name = target_user.map(|u| u.name.clone()).unwrap_or_else(|| target_id.clone());
+
+    if target_id == user_id {
+        let response = CreateInteractionResponseMessage::new()
+            .content("You already own your own wrapper!")
+            .ephemeral(true);
+        let _ = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await;
+        return;
+    }
+
+    match wrapper.transfer_ownership(user_id, &target_id).await {
+        Ok(_) => {
+            let content = format!(
+                "**Wrapper Ownership Transferred**\n\n<@{}> (`{}`) is now the owner of this wrapper, keeping its existing share list.\n\nThey can check it with `/share available`.",
+                target_id, target_name,
+            );
+            let response = CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(false);
+            if let Err(e) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                .await
+            {
+                error!("Failed to send transfer confirmation: {}", e);
+            }
+        }
+        Err(e) => {
+            error!("Failed to transfer wrapper ownership: {}", e);
+            let response = CreateInteractionResponseMessage::new()
+                .content(format!("Failed to transfer ownership: {}", e))
+                .ephemeral(true);
+            let _ = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                .await;
+        }
+    }
+}
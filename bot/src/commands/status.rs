@@ -7,6 +7,10 @@ use serenity::all::{
 use tracing::{error, info};
 
 use crate::client::{TaskStatus, WrapperClient};
+use crate::commands::{
+    build_approval_components, output_attachment, output_preview, split_into_chunks, truncate_chars,
+    OUTPUT_ATTACHMENT_THRESHOLD,
+};
 
 /// Create the command registration.
 pub fn register() -> CreateCommand {
@@ -63,38 +67,46 @@ pub async fn status(ctx: &Context, command: &CommandInteraction, wrapper: &Wrapp
 
             // Track if we need follow-up messages for full output
             let mut followup_chunks: Vec<String> = Vec::new();
+            let mut attachment = None;
 
             // Add output if present
             if !response.output.is_empty() {
-                let max_initial = 1200; // Leave room for status info
-                let max_chunk = 1900;   // Discord limit is 2000
-
-                if response.output.len() <= max_initial {
-                    content.push_str(&format!("\n\n**Output:**\n```\n{}\n```", response.output));
-                } else {
-                    // Calculate remaining length and number of follow-up chunks needed
-                    let remaining_len = response.output.len() - max_initial;
-                    let followup_count = (remaining_len + max_chunk - 1) / max_chunk; // Ceiling division
-                    let total_chunks = 1 + followup_count;
+                let char_count = response.output.chars().count();
 
-                    // First chunk in initial message
+                if char_count > OUTPUT_ATTACHMENT_THRESHOLD {
+                    // Too long to usefully split across messages - attach
+                    // the full output and show a short preview instead.
                     content.push_str(&format!(
-                        "\n\n**Output (1/{}):**\n```\n{}\n```",
-                        total_chunks,
-                        &response.output[..max_initial]
+                        "\n\n**Output:** {} chars, see attached file\n```\n{}...\n```",
+                        char_count,
+                        output_preview(&response.output),
                     ));
-
-                    // Split remaining output into chunks
-                    let remaining = &response.output[max_initial..];
-                    let mut chunk_num = 2;
-
-                    for chunk in remaining.as_bytes().chunks(max_chunk) {
-                        let chunk_str = String::from_utf8_lossy(chunk);
-                        followup_chunks.push(format!(
-                            "**Output ({}/{}):**\n```\n{}\n```",
-                            chunk_num, total_chunks, chunk_str
+                    attachment = Some(output_attachment(&response.task_id, &response.output));
+                } else {
+                    let max_initial = 1200; // Leave room for status info
+                    let max_chunk = 1900; // Discord limit is 2000
+
+                    if char_count <= max_initial {
+                        content.push_str(&format!("\n\n**Output:**\n```\n{}\n```", response.output));
+                    } else {
+                        let initial = truncate_chars(&response.output, max_initial);
+                        let remaining = &response.output[initial.len()..];
+                        let remaining_chunks = split_into_chunks(remaining, max_chunk);
+                        let total_chunks = 1 + remaining_chunks.len();
+
+                        content.push_str(&format!(
+                            "\n\n**Output (1/{}):**\n```\n{}\n```",
+                            total_chunks, initial
                         ));
-                        chunk_num += 1;
+
+                        for (i, chunk) in remaining_chunks.into_iter().enumerate() {
+                            followup_chunks.push(format!(
+                                "**Output ({}/{}):**\n```\n{}\n```",
+                                i + 2,
+                                total_chunks,
+                                chunk
+                            ));
+                        }
                     }
                 }
             }
@@ -104,23 +116,17 @@ pub async fn status(ctx: &Context, command: &CommandInteraction, wrapper: &Wrapp
                 content.push_str(&format!("\n\n**Error:**\n```\n{}\n```", err));
             }
 
-            // Add approval info if present
+            // Add approval info if present, as clickable buttons
+            let mut response_msg = CreateInteractionResponseMessage::new();
             if let Some(approval) = &response.approval_request {
-                content.push_str(&format!(
-                    "\n\n**Awaiting Approval:**\n{}\n\nOptions:\n{}",
-                    approval.description,
-                    approval
-                        .options
-                        .iter()
-                        .map(|o| format!("- `{}`: {}", o.id, o.label))
-                        .collect::<Vec<_>>()
-                        .join("\n"),
-                ));
+                content.push_str(&format!("\n\n**Awaiting Approval:**\n{}", approval.description));
+                response_msg = response_msg.components(build_approval_components(&response.task_id, approval));
             }
 
-            let response_msg = CreateInteractionResponseMessage::new()
-                .content(content)
-                .ephemeral(false);
+            let mut response_msg = response_msg.content(content).ephemeral(false);
+            if let Some(attachment) = attachment {
+                response_msg = response_msg.add_file(attachment);
+            }
 
             if let Err(e) = command
                 .create_response(&ctx.http, CreateInteractionResponse::Message(response_msg))
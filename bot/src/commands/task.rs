@@ -1,96 +1,227 @@
-//! /task command - Submit a task to Claude.
+//! /task command - Submit a task to Claude, or cancel one in flight.
 
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
 use serenity::all::{
     CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
-    CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse,
+    EditInteractionResponse,
 };
 use tracing::{error, info};
 
-use crate::client::{ExecutionMode, TaskRequest, TaskStatus, WrapperClient};
+use crate::client::{ExecutionMode, TaskEvent, TaskRequest, TaskResponse, TaskStatus, WrapperClient};
+use crate::commands::{
+    build_approval_components, output_attachment, output_preview, truncate_chars, OUTPUT_ATTACHMENT_THRESHOLD,
+};
+use crate::db::{NewTaskRecord, TaskStore};
+use crate::jobs::{JobStore, NewJobRequest};
+
+/// How often `handle_run` polls `get_task` for progress while a task is
+/// `Pending`/`Running`.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Stop polling and leave the last-seen status in place after this long,
+/// pointing the user at `/status` instead of holding the interaction open
+/// indefinitely. Comfortably under Discord's 15-minute deferred-response
+/// edit window.
+const MAX_POLL_DURATION: Duration = Duration::from_secs(600);
+
+/// Task IDs currently believed to be running against some user's wrapper,
+/// tracked so a graceful shutdown can attempt to cancel them. This is a
+/// best-effort, in-memory registry — it does not survive a bot restart.
+static INFLIGHT_TASKS: OnceLock<Mutex<HashSet<(String, String)>>> = OnceLock::new();
+
+fn inflight() -> &'static Mutex<HashSet<(String, String)>> {
+    INFLIGHT_TASKS.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+fn track(task_id: &str, user_id: &str) {
+    inflight().lock().unwrap().insert((task_id.to_string(), user_id.to_string()));
+}
+
+fn untrack(task_id: &str, user_id: &str) {
+    inflight().lock().unwrap().remove(&(task_id.to_string(), user_id.to_string()));
+}
+
+/// Snapshot of `(task_id, user_id)` pairs believed to still be in flight.
+pub fn inflight_tasks() -> Vec<(String, String)> {
+    inflight().lock().unwrap().iter().cloned().collect()
+}
+
+/// Whether `user_id` is the one who submitted `task_id`, per this process's
+/// in-memory tracking. Used as a defense-in-depth check before forwarding an
+/// approval button/modal click to the wrapper, which remains the source of
+/// truth for authorization — this just avoids a pointless round-trip (and a
+/// confusing "not authorized" error from someone else's click) when we
+/// already know the clicking user isn't the task's owner.
+pub fn is_owner(task_id: &str, user_id: &str) -> bool {
+    inflight().lock().unwrap().contains(&(task_id.to_string(), user_id.to_string()))
+}
 
 /// Create the command registration.
 pub fn register() -> CreateCommand {
     CreateCommand::new("task")
-        .description("Submit a task to Claude Code")
+        .description("Submit a task to Claude Code, or cancel one in flight")
         .add_option(
             CreateCommandOption::new(
-                CommandOptionType::String,
-                "prompt",
-                "The task/prompt to send to Claude",
+                CommandOptionType::SubCommand,
+                "run",
+                "Submit a new task to Claude Code",
             )
-            .required(true),
-        )
-        .add_option(
-            CreateCommandOption::new(
-                CommandOptionType::String,
-                "project",
-                "Project name to work on (use /project list to see available)",
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "prompt",
+                    "The task/prompt to send to Claude",
+                )
+                .required(true),
             )
-            .required(false),
-        )
-        .add_option(
-            CreateCommandOption::new(
-                CommandOptionType::User,
-                "target",
-                "Use another user's wrapper (requires their permission via /share)",
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "project",
+                    "Project name to work on (use /project list to see available)",
+                )
+                .required(false)
+                .set_autocomplete(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "target",
+                    "Use another user's wrapper (requires their permission via /share)",
+                )
+                .required(false),
             )
-            .required(false),
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "mode",
+                    "Where to run: local (your machine) or cluster (Pi nodes)",
+                )
+                .required(false)
+                .add_string_choice("Local (your machine)", "local")
+                .add_string_choice("Cluster (Pi nodes)", "cluster"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "session",
+                    "Optional session ID to continue a previous session",
+                )
+                .required(false),
+            ),
         )
         .add_option(
             CreateCommandOption::new(
-                CommandOptionType::String,
-                "mode",
-                "Where to run: local (your machine) or cluster (Pi nodes)",
+                CommandOptionType::SubCommand,
+                "cancel",
+                "Cancel a running or pending task",
             )
-            .required(false)
-            .add_string_choice("Local (your machine)", "local")
-            .add_string_choice("Cluster (Pi nodes)", "cluster"),
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "task_id",
+                    "The task ID to cancel",
+                )
+                .required(true),
+            ),
         )
         .add_option(
             CreateCommandOption::new(
-                CommandOptionType::String,
-                "session",
-                "Optional session ID to continue a previous session",
+                CommandOptionType::SubCommand,
+                "queue",
+                "Queue a task to run in the background against your wrapper or cluster, without waiting on it",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "prompt",
+                    "The task/prompt to send to Claude",
+                )
+                .required(true),
             )
-            .required(false),
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "project",
+                    "Project name to work on (use /project list to see available)",
+                )
+                .required(false)
+                .set_autocomplete(true),
+            ),
         )
 }
 
 /// Handle the /task command.
-pub async fn task(ctx: &Context, command: &CommandInteraction, wrapper: &WrapperClient) {
+pub async fn task(
+    ctx: &Context,
+    command: &CommandInteraction,
+    wrapper: &WrapperClient,
+    db: &TaskStore,
+    jobs: &JobStore,
+) {
+    let subcommand = command
+        .data
+        .options
+        .first()
+        .map(|opt| opt.name.as_str())
+        .unwrap_or("run");
+
+    let sub_opts = command
+        .data
+        .options
+        .first()
+        .and_then(|opt| {
+            if let serenity::all::CommandDataOptionValue::SubCommand(opts) = &opt.value {
+                Some(opts.clone())
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    match subcommand {
+        "cancel" => handle_cancel(ctx, command, wrapper, &sub_opts).await,
+        "queue" => handle_queue(ctx, command, jobs, &sub_opts).await,
+        _ => handle_run(ctx, command, wrapper, db, &sub_opts).await,
+    }
+}
+
+async fn handle_run(
+    ctx: &Context,
+    command: &CommandInteraction,
+    wrapper: &WrapperClient,
+    db: &TaskStore,
+    sub_opts: &[serenity::all::CommandDataOption],
+) {
     // Extract user info
     let user_id = command.user.id.to_string();
 
     // Extract options
-    let prompt = command
-        .data
-        .options
+    let prompt = sub_opts
         .iter()
         .find(|opt| opt.name == "prompt")
         .and_then(|opt| opt.value.as_str())
         .unwrap_or("")
         .to_string();
 
-    let project = command
-        .data
-        .options
+    let project = sub_opts
         .iter()
         .find(|opt| opt.name == "project")
         .and_then(|opt| opt.value.as_str())
         .map(|s| s.to_string());
 
     // Extract target user for collaborative access using Serenity 0.12 API
-    let target_user = command
-        .data
-        .options
+    let target_user = sub_opts
         .iter()
         .find(|opt| opt.name == "target")
         .and_then(|opt| opt.value.as_user_id())
         .map(|user_id| user_id.to_string());
 
-    let mode = command
-        .data
-        .options
+    let mode = sub_opts
         .iter()
         .find(|opt| opt.name == "mode")
         .and_then(|opt| opt.value.as_str())
@@ -99,9 +230,7 @@ pub async fn task(ctx: &Context, command: &CommandInteraction, wrapper: &Wrapper
             _ => ExecutionMode::Local,
         });
 
-    let session_id = command
-        .data
-        .options
+    let session_id = sub_opts
         .iter()
         .find(|opt| opt.name == "session")
         .and_then(|opt| opt.value.as_str())
@@ -112,105 +241,73 @@ pub async fn task(ctx: &Context, command: &CommandInteraction, wrapper: &Wrapper
         user_id, target_user, prompt, project, mode
     );
 
-    // Build initial response message
-    let mode_str = mode
-        .as_ref()
-        .map(|m| match m {
-            ExecutionMode::Local => " (local)",
-            ExecutionMode::Cluster => " (cluster)",
-        })
-        .unwrap_or("");
-
-    let project_info = project
-        .as_ref()
-        .map(|p| format!(" on `{}`", p))
-        .unwrap_or_default();
-
-    let target_info = target_user
-        .as_ref()
-        .map(|t| format!(" via <@{}>", t))
-        .unwrap_or_default();
-
-    let initial_response = CreateInteractionResponseMessage::new()
-        .content(format!(
-            "Processing your task{}{}{}...",
-            project_info, target_info, mode_str
-        ))
-        .ephemeral(false);
-
-    if let Err(e) = command
-        .create_response(&ctx.http, CreateInteractionResponse::Message(initial_response))
-        .await
-    {
-        error!("Failed to send initial response: {}", e);
+    // Defer immediately so the wrapper round-trip below can't blow past
+    // Discord's 3-second initial-response window.
+    if crate::commands::defer(ctx, command, false).await.is_err() {
         return;
     }
 
     // Submit task to wrapper service
+    let project_for_record = project.clone();
+    let mode_for_record = mode.clone().unwrap_or(ExecutionMode::Local);
     let request = TaskRequest {
         prompt: prompt.clone(),
         project,
         session_id,
         working_dir: None,
-        discord_user_id: Some(user_id),
+        discord_user_id: Some(user_id.clone()),
         target_user_id: target_user,
         mode,
+        delegated_token: crate::commands::auth_token_for(&user_id),
     };
 
     match wrapper.submit_task(request).await {
-        Ok(response) => {
-            let status_emoji = match response.status {
-                TaskStatus::Completed => "✅",
-                TaskStatus::Failed => "❌",
-                TaskStatus::Running => "🔄",
-                TaskStatus::Pending => "⏳",
-                TaskStatus::NeedsApproval => "⚠️",
-            };
+        Ok(mut response) => {
+            if matches!(response.status, TaskStatus::Pending | TaskStatus::Running | TaskStatus::NeedsApproval) {
+                track(&response.task_id, &user_id);
+                // So the task-events consumer can post a completion notice
+                // even if this interaction's polling loop below times out
+                // or the bot restarts before the task finishes.
+                crate::notify::register_target(&response.task_id, command.channel_id, command.user.id);
+            }
 
-            let mut content = format!(
-                "{} **Task {}**\n\n**Status:** {}\n**Task ID:** `{}`\n**Session:** `{}`",
-                status_emoji,
-                response.status,
-                response.status,
-                response.task_id,
-                response.session_id,
-            );
+            let record = NewTaskRecord {
+                task_id: response.task_id.clone(),
+                session_id: response.session_id.clone(),
+                discord_user_id: user_id.clone(),
+                channel_id: command.channel_id.get(),
+                project: project_for_record,
+                mode: match mode_for_record {
+                    ExecutionMode::Local => "local".to_string(),
+                    ExecutionMode::Cluster => "cluster".to_string(),
+                },
+            };
+            if let Err(e) = db.record_task(record).await {
+                error!("Failed to persist task {}: {}", response.task_id, e);
+            }
 
-            // Add output (truncated if too long for Discord's 2000 char limit)
-            if !response.output.is_empty() {
-                let max_output_len = 1500; // Leave room for status, task ID, etc.
-                let output = if response.output.len() > max_output_len {
-                    format!(
-                        "{}...\n\n>>> (truncated - {} chars total) <<<\nUse `/status task_id:{}` for full output",
-                        &response.output[..max_output_len],
-                        response.output.len(),
-                        response.task_id
-                    )
-                } else {
-                    response.output.clone()
-                };
-                content.push_str(&format!("\n\n**Output:**\n```\n{}\n```", output));
-            }
-
-            // Add error if present
-            if let Some(err) = &response.error {
-                content.push_str(&format!("\n\n**Error:**\n```\n{}\n```", err));
-            }
-
-            // Add approval request if present
-            if let Some(approval) = &response.approval_request {
-                content.push_str(&format!(
-                    "\n\n**Approval Required:**\n{}\n\nUse `/approve task_id:{} option:<option>` to respond.",
-                    approval.description,
-                    response.task_id,
-                ));
-            }
-
-            // Update the response
-            let edit = EditInteractionResponse::new().content(content);
-            if let Err(e) = command.edit_response(&ctx.http, edit).await {
+            if let Err(e) = edit_task_message(ctx, command, &response).await {
                 error!("Failed to edit response: {}", e);
             }
+
+            // Live progress: prefer the wrapper's WebSocket event stream so
+            // output shows up as it's produced, falling back to polling
+            // `get_task` if the stream can't be opened (e.g. an older
+            // wrapper version). If an opened stream drops before `Done`,
+            // there is no polling fallback: the message keeps its
+            // last-seen state.
+            if matches!(response.status, TaskStatus::Pending | TaskStatus::Running) {
+                let delegated_token = crate::commands::auth_token_for(&user_id);
+                match wrapper.stream_task(&response.task_id, &user_id, delegated_token.as_deref()).await {
+                    Ok(stream) => {
+                        stream_task_progress(ctx, command, db, &user_id, &mut response, stream).await;
+                    }
+                    Err(e) => {
+                        error!("Failed to open task event stream for {}, falling back to polling: {}", response.task_id, e);
+                        poll_task_progress(ctx, command, wrapper, db, &user_id, &mut response).await;
+                    }
+                }
+            }
         }
         Err(e) => {
             error!("Task submission failed: {}", e);
@@ -231,3 +328,295 @@ pub async fn task(ctx: &Context, command: &CommandInteraction, wrapper: &Wrapper
         }
     }
 }
+
+/// Drive `handle_run`'s progress loop from the wrapper's WebSocket event
+/// stream, editing the response whenever an event changes what the user
+/// would see. Stops once the stream yields `Done`, errors, or closes, or
+/// after `MAX_POLL_DURATION` — whichever comes first.
+async fn stream_task_progress(
+    ctx: &Context,
+    command: &CommandInteraction,
+    db: &TaskStore,
+    user_id: &str,
+    response: &mut TaskResponse,
+    mut stream: impl futures_util::Stream<Item = anyhow::Result<TaskEvent>> + Unpin,
+) {
+    let deadline = tokio::time::Instant::now() + MAX_POLL_DURATION;
+
+    loop {
+        let event = tokio::select! {
+            _ = tokio::time::sleep_until(deadline) => break,
+            item = stream.next() => item,
+        };
+
+        let event = match event {
+            Some(Ok(event)) => event,
+            Some(Err(e)) => {
+                error!("Task event stream error for {}: {}", response.task_id, e);
+                break;
+            }
+            None => break,
+        };
+
+        match event {
+            TaskEvent::OutputChunk { text } => {
+                response.output.push_str(&text);
+            }
+            TaskEvent::StatusChanged { status } => {
+                response.status = status;
+                if let Err(e) = db.update_status(&response.task_id, &response.status).await {
+                    error!("Failed to persist status for task {}: {}", response.task_id, e);
+                }
+            }
+            TaskEvent::ApprovalNeeded { request } => {
+                response.approval_request = Some(request);
+            }
+            TaskEvent::Done { response: done } => {
+                *response = done;
+                if let Err(e) = db.update_status(&response.task_id, &response.status).await {
+                    error!("Failed to persist status for task {}: {}", response.task_id, e);
+                }
+                untrack(&response.task_id, user_id);
+                if let Err(e) = edit_task_message(ctx, command, response).await {
+                    error!("Failed to edit response: {}", e);
+                }
+                return;
+            }
+            TaskEvent::Unknown => continue,
+        }
+
+        if let Err(e) = edit_task_message(ctx, command, response).await {
+            error!("Failed to edit response: {}", e);
+        }
+    }
+
+    if matches!(response.status, TaskStatus::Completed | TaskStatus::Failed) {
+        untrack(&response.task_id, user_id);
+    }
+}
+
+/// Fallback progress loop used when the wrapper's event stream can't be
+/// opened: poll `get_task` until the status goes terminal (or we hit
+/// `MAX_POLL_DURATION`), editing the response whenever the status or output
+/// actually changes instead of leaving a frozen "Pending"/"Running" message
+/// for the whole task.
+async fn poll_task_progress(
+    ctx: &Context,
+    command: &CommandInteraction,
+    wrapper: &WrapperClient,
+    db: &TaskStore,
+    user_id: &str,
+    response: &mut TaskResponse,
+) {
+    let deadline = Instant::now() + MAX_POLL_DURATION;
+    while matches!(response.status, TaskStatus::Pending | TaskStatus::Running) && Instant::now() < deadline {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let polled = match wrapper.get_task(&response.task_id, user_id).await {
+            Ok(polled) => polled,
+            Err(e) => {
+                error!("Failed to poll task {}: {}", response.task_id, e);
+                break;
+            }
+        };
+
+        let changed = polled.status != response.status || polled.output.len() != response.output.len();
+        *response = polled;
+
+        if changed {
+            if let Err(e) = db.update_status(&response.task_id, &response.status).await {
+                error!("Failed to persist status for task {}: {}", response.task_id, e);
+            }
+        }
+
+        if matches!(response.status, TaskStatus::Completed | TaskStatus::Failed) {
+            untrack(&response.task_id, user_id);
+        }
+
+        if changed {
+            if let Err(e) = edit_task_message(ctx, command, response).await {
+                error!("Failed to edit response: {}", e);
+            }
+        }
+    }
+}
+
+/// Render a `TaskResponse` as Discord message content (plus approval
+/// buttons, if any) and push it as an edit to the deferred interaction
+/// response. Shared between the initial `submit_task` result and every
+/// subsequent poll in `handle_run`'s progress loop.
+async fn edit_task_message(
+    ctx: &Context,
+    command: &CommandInteraction,
+    response: &TaskResponse,
+) -> serenity::Result<()> {
+    let status_emoji = match response.status {
+        TaskStatus::Completed => "✅",
+        TaskStatus::Failed => "❌",
+        TaskStatus::Running => "🔄",
+        TaskStatus::Pending => "⏳",
+        TaskStatus::NeedsApproval => "⚠️",
+    };
+
+    let mut content = format!(
+        "{} **Task {}**\n\n**Status:** {}\n**Task ID:** `{}`\n**Session:** `{}`",
+        status_emoji, response.status, response.status, response.task_id, response.session_id,
+    );
+
+    // Add output (truncated, or attached as a file if long enough that
+    // truncating it wouldn't leave anything useful inline)
+    let mut attachment = None;
+    if !response.output.is_empty() {
+        let char_count = response.output.chars().count();
+
+        if char_count > OUTPUT_ATTACHMENT_THRESHOLD {
+            content.push_str(&format!(
+                "\n\n**Output:** {} chars, see attached file\n```\n{}...\n```",
+                char_count,
+                output_preview(&response.output),
+            ));
+            attachment = Some(output_attachment(&response.task_id, &response.output));
+        } else {
+            let max_output_len = 1500; // Leave room for status, task ID, etc.
+            let output = if char_count > max_output_len {
+                format!(
+                    "{}...\n\n>>> (truncated - {} chars total) <<<\nUse `/status task_id:{}` for full output",
+                    truncate_chars(&response.output, max_output_len),
+                    char_count,
+                    response.task_id
+                )
+            } else {
+                response.output.clone()
+            };
+            content.push_str(&format!("\n\n**Output:**\n```\n{}\n```", output));
+        }
+    }
+
+    // Add error if present
+    if let Some(err) = &response.error {
+        content.push_str(&format!("\n\n**Error:**\n```\n{}\n```", err));
+    }
+
+    // Add approval request if present, rendered as clickable buttons
+    // instead of a typed `/approve` hint.
+    let mut edit = EditInteractionResponse::new();
+    if let Some(approval) = &response.approval_request {
+        content.push_str(&format!("\n\n**Approval Required:**\n{}", approval.description));
+        edit = edit.components(build_approval_components(&response.task_id, approval));
+    }
+
+    edit = edit.content(content);
+    if let Some(attachment) = attachment {
+        edit = edit.new_attachment(attachment);
+    }
+    command.edit_response(&ctx.http, edit).await.map(|_| ())
+}
+
+async fn handle_cancel(
+    ctx: &Context,
+    command: &CommandInteraction,
+    wrapper: &WrapperClient,
+    sub_opts: &[serenity::all::CommandDataOption],
+) {
+    let user_id = command.user.id.to_string();
+
+    let task_id = sub_opts
+        .iter()
+        .find(|opt| opt.name == "task_id")
+        .and_then(|opt| opt.value.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    info!("Task cancel requested: task_id='{}', user={}", task_id, user_id);
+
+    if crate::commands::defer(ctx, command, false).await.is_err() {
+        return;
+    }
+
+    // `cancel_task` itself verifies the caller owns or has shared access to
+    // the wrapper running the task; we don't duplicate that check here.
+    match wrapper.cancel_task(&task_id, &user_id).await {
+        Ok(response) => {
+            untrack(&task_id, &user_id);
+
+            let mut content = format!(
+                "🛑 **Task Cancelled**\n\n**Status:** {}\n**Task ID:** `{}`",
+                response.status, response.task_id,
+            );
+
+            if !response.output.is_empty() {
+                content.push_str(&format!("\n\n**Partial Output:**\n```\n{}\n```", response.output));
+            }
+
+            let edit = EditInteractionResponse::new().content(content);
+            if let Err(e) = command.edit_response(&ctx.http, edit).await {
+                error!("Failed to edit cancel response: {}", e);
+            }
+        }
+        Err(e) => {
+            error!("Task cancellation failed: {}", e);
+            let edit = EditInteractionResponse::new()
+                .content(format!("❌ **Cancel Failed**\n\n```\n{}\n```", e));
+            if let Err(e) = command.edit_response(&ctx.http, edit).await {
+                error!("Failed to edit cancel error response: {}", e);
+            }
+        }
+    }
+}
+
+/// Handle `/task queue`: enqueue a job on the durable `JobStore` instead of
+/// submitting to the wrapper directly. `dispatch::run` picks it up in the
+/// background, resolves a target, and notifies the caller's channel once it
+/// reaches a terminal state - useful for a prompt the caller doesn't want to
+/// keep the interaction open for.
+async fn handle_queue(
+    ctx: &Context,
+    command: &CommandInteraction,
+    jobs: &JobStore,
+    sub_opts: &[serenity::all::CommandDataOption],
+) {
+    let user_id = command.user.id.to_string();
+
+    let prompt = sub_opts
+        .iter()
+        .find(|opt| opt.name == "prompt")
+        .and_then(|opt| opt.value.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    let project = sub_opts
+        .iter()
+        .find(|opt| opt.name == "project")
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string());
+
+    info!("Task queue requested: user={}, prompt='{}', project={:?}", user_id, prompt, project);
+
+    if crate::commands::defer(ctx, command, false).await.is_err() {
+        return;
+    }
+
+    let request = NewJobRequest {
+        discord_id: user_id,
+        channel_id: command.channel_id.get(),
+        prompt,
+        project,
+    };
+
+    let content = match jobs.submit_job(request).await {
+        Ok(job_id) => format!(
+            "📥 **Task Queued**\n\n**Job ID:** `{}`\n\nYou'll be notified in this channel once it finishes. \
+             Check `/register status` for its progress in the meantime.",
+            job_id
+        ),
+        Err(e) => {
+            error!("Failed to queue job: {}", e);
+            format!("❌ **Failed to queue task**\n\n```\n{}\n```", e)
+        }
+    };
+
+    let edit = EditInteractionResponse::new().content(content);
+    if let Err(e) = command.edit_response(&ctx.http, edit).await {
+        error!("Failed to edit queue response: {}", e);
+    }
+}
@@ -0,0 +1,51 @@
+//! /tasks command - List the caller's recent submitted tasks.
+
+use serenity::all::{
+    CommandInteraction, Context, CreateCommand, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+use tracing::error;
+
+use crate::db::TaskStore;
+
+/// How many recent tasks `/tasks` shows.
+const RECENT_LIMIT: i64 = 10;
+
+/// Create the command registration.
+pub fn register() -> CreateCommand {
+    CreateCommand::new("tasks").description("List your recent tasks and their last-known status")
+}
+
+/// Handle the /tasks command.
+pub async fn tasks(ctx: &Context, command: &CommandInteraction, db: &TaskStore) {
+    let user_id = command.user.id.to_string();
+
+    let content = match db.recent_for_user(&user_id, RECENT_LIMIT).await {
+        Ok(records) if records.is_empty() => "You haven't submitted any tasks yet.".to_string(),
+        Ok(records) => {
+            let lines: Vec<String> = records
+                .iter()
+                .map(|r| {
+                    format!(
+                        "`{}` - **{}** ({}{}) - {}",
+                        r.task_id,
+                        r.status,
+                        r.mode,
+                        r.project.as_ref().map(|p| format!(", {}", p)).unwrap_or_default(),
+                        r.created_at,
+                    )
+                })
+                .collect();
+            format!("**Your Recent Tasks**\n\n{}", lines.join("\n"))
+        }
+        Err(e) => {
+            error!("Failed to query recent tasks: {}", e);
+            "❌ Failed to load your task history.".to_string()
+        }
+    };
+
+    let response = CreateInteractionResponseMessage::new().content(content).ephemeral(true);
+    if let Err(e) = command.create_response(&ctx.http, CreateInteractionResponse::Message(response)).await {
+        error!("Failed to send tasks response: {}", e);
+    }
+}
@@ -17,6 +17,37 @@ pub struct Config {
 
     /// Log level
     pub log_level: String,
+
+    /// Discord role IDs treated as administrators, in addition to anyone
+    /// holding the `ADMINISTRATOR` permission.
+    pub admin_role_ids: Vec<u64>,
+
+    /// Discord role IDs allowed to register or share wrappers. Empty means
+    /// unrestricted (the behavior before this setting existed).
+    pub allowed_role_ids: Vec<u64>,
+
+    /// Guild IDs commands are restricted to. Empty means unrestricted.
+    pub allowed_guild_ids: Vec<u64>,
+
+    /// Channel IDs commands are restricted to. Empty means unrestricted.
+    pub allowed_channel_ids: Vec<u64>,
+
+    /// When true, every command is rejected except for admins (see
+    /// `admin_role_ids`). Lets the bot be taken offline for maintenance
+    /// without a restart.
+    pub maintenance_mode: bool,
+
+    /// Postgres connection string for the persistent task registry.
+    pub database_url: String,
+
+    /// Path to the SQLite database backing the durable job dispatch queue
+    /// (see `jobs::JobStore`).
+    pub jobs_database_path: String,
+
+    /// Path to a `cluster::ClusterMetadata` JSON file describing the Pi
+    /// cluster's nodes, if any. Missing means no cluster nodes are
+    /// dispatchable - jobs always go to the submitting user's local wrapper.
+    pub cluster_metadata_path: Option<String>,
 }
 
 impl Config {
@@ -37,11 +68,44 @@ impl Config {
         let log_level = env::var("RUST_LOG")
             .unwrap_or_else(|_| "info".to_string());
 
+        let admin_role_ids = parse_id_list("ADMIN_ROLE_IDS");
+        let allowed_role_ids = parse_id_list("ALLOWED_ROLE_IDS");
+        let allowed_guild_ids = parse_id_list("ALLOWED_GUILD_IDS");
+        let allowed_channel_ids = parse_id_list("ALLOWED_CHANNEL_IDS");
+
+        let maintenance_mode = env::var("MAINTENANCE_MODE")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        let database_url = env::var("DATABASE_URL")
+            .context("DATABASE_URL environment variable not set")?;
+
+        let jobs_database_path = env::var("JOBS_DATABASE_PATH").unwrap_or_else(|_| "jobs.db".to_string());
+        let cluster_metadata_path = env::var("CLUSTER_METADATA_PATH").ok();
+
         Ok(Self {
             discord_token,
             guild_id,
             wrapper_url,
             log_level,
+            admin_role_ids,
+            allowed_role_ids,
+            allowed_guild_ids,
+            allowed_channel_ids,
+            maintenance_mode,
+            database_url,
+            jobs_database_path,
+            cluster_metadata_path,
         })
     }
 }
+
+/// Parse a comma-separated list of Discord snowflake IDs from an env var,
+/// ignoring entries that don't parse. Missing or empty means "none".
+fn parse_id_list(var: &str) -> Vec<u64> {
+    env::var(var)
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|s| s.trim().parse().ok())
+        .collect()
+}
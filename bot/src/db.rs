@@ -0,0 +1,160 @@
+//! Persistent record of submitted tasks, backed by a pooled Postgres store.
+//!
+//! `commands::task`'s in-flight tracking and `notify`'s target registry are
+//! both best-effort, in-memory, and lost on restart. This module gives both
+//! a durable backing so `/tasks` can list a user's history and completion
+//! notices can still find their channel/user after the bot restarts mid-task.
+
+use bb8::Pool;
+use bb8_postgres::PostgresConnectionManager;
+use tokio_postgres::NoTls;
+
+use anyhow::{Context, Result};
+
+use crate::client::TaskStatus;
+
+/// A freshly submitted task, as recorded right after `submit_task` returns.
+#[derive(Debug, Clone)]
+pub struct NewTaskRecord {
+    pub task_id: String,
+    pub session_id: String,
+    pub discord_user_id: String,
+    pub channel_id: u64,
+    pub project: Option<String>,
+    pub mode: String,
+}
+
+/// A row from the `tasks` table, as returned by `/tasks`.
+#[derive(Debug, Clone)]
+pub struct TaskRecord {
+    pub task_id: String,
+    pub session_id: String,
+    pub discord_user_id: String,
+    pub channel_id: u64,
+    pub project: Option<String>,
+    pub mode: String,
+    pub status: String,
+    pub created_at: String,
+}
+
+impl TaskRecord {
+    fn from_row(row: &tokio_postgres::Row) -> Self {
+        Self {
+            task_id: row.get("task_id"),
+            session_id: row.get("session_id"),
+            discord_user_id: row.get("discord_user_id"),
+            channel_id: row.get::<_, i64>("channel_id") as u64,
+            project: row.get("project"),
+            mode: row.get("mode"),
+            status: row.get("status"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+/// Pooled Postgres client for the `tasks` table. Cheap to clone - the
+/// underlying `bb8::Pool` is itself an `Arc`.
+#[derive(Clone)]
+pub struct TaskStore {
+    pool: Pool<PostgresConnectionManager<NoTls>>,
+}
+
+impl TaskStore {
+    /// Connect to Postgres, create the pool, and ensure the `tasks` table
+    /// exists.
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let manager = PostgresConnectionManager::new_from_stringlike(database_url, NoTls)
+            .context("Failed to parse DATABASE_URL")?;
+        let pool = Pool::builder()
+            .build(manager)
+            .await
+            .context("Failed to build Postgres connection pool")?;
+
+        let store = Self { pool };
+        store.ensure_schema().await?;
+        Ok(store)
+    }
+
+    async fn ensure_schema(&self) -> Result<()> {
+        let conn = self.pool.get().await.context("Failed to get pooled connection")?;
+        conn.batch_execute(
+            "CREATE TABLE IF NOT EXISTS tasks (
+                task_id TEXT PRIMARY KEY,
+                session_id TEXT NOT NULL,
+                discord_user_id TEXT NOT NULL,
+                channel_id BIGINT NOT NULL,
+                project TEXT,
+                mode TEXT NOT NULL,
+                status TEXT NOT NULL,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )",
+        )
+        .await
+        .context("Failed to create tasks table")?;
+        Ok(())
+    }
+
+    /// Record a newly submitted task as `pending`. A no-op if the task ID
+    /// is already recorded (the wrapper retried submission, say).
+    pub async fn record_task(&self, record: NewTaskRecord) -> Result<()> {
+        let conn = self.pool.get().await.context("Failed to get pooled connection")?;
+        conn.execute(
+            "INSERT INTO tasks (task_id, session_id, discord_user_id, channel_id, project, mode, status)
+             VALUES ($1, $2, $3, $4, $5, $6, 'pending')
+             ON CONFLICT (task_id) DO NOTHING",
+            &[
+                &record.task_id,
+                &record.session_id,
+                &record.discord_user_id,
+                &(record.channel_id as i64),
+                &record.project,
+                &record.mode,
+            ],
+        )
+        .await
+        .context("Failed to record task")?;
+        Ok(())
+    }
+
+    /// Update the stored status for `task_id` as it changes.
+    pub async fn update_status(&self, task_id: &str, status: &TaskStatus) -> Result<()> {
+        let conn = self.pool.get().await.context("Failed to get pooled connection")?;
+        conn.execute(
+            "UPDATE tasks SET status = $1 WHERE task_id = $2",
+            &[&status.to_string(), &task_id],
+        )
+        .await
+        .context("Failed to update task status")?;
+        Ok(())
+    }
+
+    /// The `limit` most recent tasks submitted by `discord_user_id`, newest
+    /// first.
+    pub async fn recent_for_user(&self, discord_user_id: &str, limit: i64) -> Result<Vec<TaskRecord>> {
+        let conn = self.pool.get().await.context("Failed to get pooled connection")?;
+        let rows = conn
+            .query(
+                "SELECT task_id, session_id, discord_user_id, channel_id, project, mode, status, created_at::text AS created_at
+                 FROM tasks WHERE discord_user_id = $1 ORDER BY created_at DESC LIMIT $2",
+                &[&discord_user_id, &limit],
+            )
+            .await
+            .context("Failed to query recent tasks")?;
+        Ok(rows.iter().map(TaskRecord::from_row).collect())
+    }
+
+    /// Where to deliver a completion notice for `task_id`: its channel and
+    /// submitting user, looked up when `notify`'s in-memory target registry
+    /// doesn't have it (e.g. the bot restarted while the task was running).
+    pub async fn notify_target(&self, task_id: &str) -> Result<Option<(u64, String)>> {
+        let conn = self.pool.get().await.context("Failed to get pooled connection")?;
+        let row = conn
+            .query_opt(
+                "SELECT channel_id, discord_user_id FROM tasks WHERE task_id = $1",
+                &[&task_id],
+            )
+            .await
+            .context("Failed to query task target")?;
+        Ok(row.map(|r| (r.get::<_, i64>(0) as u64, r.get(1))))
+    }
+}
@@ -0,0 +1,226 @@
+//! Background dispatcher that drains the durable job queue (`jobs::JobStore`)
+//! onto a target wrapper or cluster node, and notifies the submitting user
+//! once each job reaches a terminal state.
+//!
+//! Complements `notify::run`: that consumer reacts to events the wrapper
+//! service already decided to push. This loop is what puts a job in front
+//! of a wrapper in the first place, for jobs submitted before a target
+//! existed to send them to.
+
+use std::time::{Duration, Instant};
+
+use serenity::all::{ChannelId, Context, CreateMessage, UserId};
+use serenity::async_trait;
+use tracing::{error, warn};
+
+use crate::client::{ExecutionMode, TaskRequest, TaskStatus, WrapperClient};
+use crate::cluster::NodeRegistry;
+use crate::jobs::{JobRecord, JobState, JobStore};
+
+/// How long the dispatcher sleeps between queue drains when nothing was
+/// claimed (and between progress polls of a job it's actively running).
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Cap on how long the dispatcher will poll a single dispatched job before
+/// giving up on it, so one stuck job (hung node, approval nobody answers)
+/// can't head-of-line-block every other `Queued` job behind it. Generous
+/// compared to `task.rs`'s `MAX_POLL_DURATION` since this runs unattended
+/// in the background rather than against a live interaction.
+const MAX_JOB_POLL_DURATION: Duration = Duration::from_secs(1800);
+
+/// Delivers a terminal-state notice for a dispatched job. Pluggable so
+/// tests (or a future non-Discord surface) can swap in something other
+/// than posting/DMing through the gateway.
+#[async_trait]
+pub trait JobNotifier: Send + Sync {
+    async fn notify(&self, job: &JobRecord);
+}
+
+/// Posts a terminal-state notice to the channel the job was submitted
+/// from, falling back to a DM if that post fails (channel deleted, bot
+/// kicked, etc.).
+pub struct DiscordJobNotifier {
+    ctx: Context,
+}
+
+impl DiscordJobNotifier {
+    pub fn new(ctx: Context) -> Self {
+        Self { ctx }
+    }
+}
+
+#[async_trait]
+impl JobNotifier for DiscordJobNotifier {
+    async fn notify(&self, job: &JobRecord) {
+        let emoji = match job.state {
+            JobState::Succeeded => "✅",
+            JobState::Failed => "❌",
+            JobState::Queued | JobState::Running => {
+                warn!("notify called for non-terminal job {}", job.job_id);
+                return;
+            }
+        };
+        let content = format!(
+            "{} <@{}> Job `{}` is now **{}**.",
+            emoji, job.discord_id, job.job_id, job.state
+        );
+
+        let channel_id = ChannelId::new(job.channel_id);
+        if channel_id
+            .send_message(&self.ctx.http, CreateMessage::new().content(&content))
+            .await
+            .is_ok()
+        {
+            return;
+        }
+
+        let Some(user_id) = job.discord_id.parse::<u64>().ok().map(UserId::new) else {
+            warn!("Job {} has an unparseable discord_id, dropping notice", job.job_id);
+            return;
+        };
+
+        match user_id.create_dm_channel(&self.ctx.http).await {
+            Ok(dm) => {
+                if let Err(e) = dm.send_message(&self.ctx.http, CreateMessage::new().content(&content)).await {
+                    error!("Failed to DM job {} completion notice: {}", job.job_id, e);
+                }
+            }
+            Err(e) => error!("Failed to open DM for job {} completion notice: {}", job.job_id, e),
+        }
+    }
+}
+
+/// Pick a target for `job`: the cluster node `registry` deterministically
+/// allocates for its project, if the submitting user has `cluster_enabled`
+/// and the allocation finds a reachable node; otherwise the submitting
+/// user's own registered local wrapper.
+async fn resolve_target(wrapper: &WrapperClient, registry: &NodeRegistry, job: &JobRecord) -> Option<String> {
+    let user = match wrapper.get_user(&job.discord_id).await {
+        Ok(user) => Some(user),
+        Err(e) => {
+            warn!("Failed to resolve a wrapper for job {}'s owner: {}", job.job_id, e);
+            None
+        }
+    };
+
+    if let Some(project) = &job.project {
+        let cluster_enabled = user.as_ref().is_some_and(|u| u.cluster_enabled);
+        if cluster_enabled && !registry.metadata().nodes().is_empty() {
+            if let Some(node_id) = registry.allocate_node(project) {
+                return Some(node_id);
+            }
+        }
+    }
+
+    user.and_then(|u| u.local_wrapper_url)
+}
+
+/// Build a `WrapperClient` pointed at `target`: a cluster node id (resolved
+/// to its address via `registry`) or, failing that, treated as a local
+/// wrapper URL directly.
+fn client_for_target(registry: &NodeRegistry, target: &str, auth_token: Option<String>) -> WrapperClient {
+    let base_url = registry
+        .metadata()
+        .nodes()
+        .iter()
+        .find(|n| n.node_id == target)
+        .map(|n| n.address.as_str())
+        .unwrap_or(target);
+
+    match auth_token {
+        Some(token) => WrapperClient::with_auth(base_url, &token),
+        None => WrapperClient::new(base_url),
+    }
+}
+
+/// Run the dispatcher loop for the life of the process: claim queued jobs,
+/// pick a target, submit and poll them to completion, and notify. Intended
+/// to be spawned once as a background task, alongside `notify::run`.
+pub async fn run(
+    wrapper: WrapperClient,
+    jobs: JobStore,
+    registry: NodeRegistry,
+    notifier: impl JobNotifier + 'static,
+) {
+    loop {
+        registry.refresh().await;
+
+        let claimed = match jobs.claim_next_queued().await {
+            Ok(Some(job)) => job,
+            Ok(None) => {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+            Err(e) => {
+                error!("Failed to claim next queued job: {}", e);
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+        };
+
+        let Some(target) = resolve_target(&wrapper, &registry, &claimed).await else {
+            warn!("No target available for job {}, marking failed", claimed.job_id);
+            if let Err(e) = jobs.mark_terminal(&claimed.job_id, JobState::Failed).await {
+                error!("Failed to mark job {} failed: {}", claimed.job_id, e);
+            }
+            notifier
+                .notify(&JobRecord { state: JobState::Failed, ..claimed })
+                .await;
+            continue;
+        };
+
+        if let Err(e) = jobs.set_target(&claimed.job_id, &target).await {
+            error!("Failed to persist target for job {}: {}", claimed.job_id, e);
+        }
+
+        let auth_token = crate::commands::auth_token_for(&claimed.discord_id);
+        let client = client_for_target(&registry, &target, auth_token.clone());
+
+        let request = TaskRequest {
+            prompt: claimed.prompt.clone(),
+            session_id: None,
+            project: claimed.project.clone(),
+            working_dir: None,
+            discord_user_id: Some(claimed.discord_id.clone()),
+            target_user_id: None,
+            mode: Some(ExecutionMode::Local),
+            delegated_token: auth_token,
+        };
+
+        let final_state = match client.submit_task(request).await {
+            Ok(mut response) => {
+                let deadline = Instant::now() + MAX_JOB_POLL_DURATION;
+                while matches!(
+                    response.status,
+                    TaskStatus::Pending | TaskStatus::Running | TaskStatus::NeedsApproval
+                ) && Instant::now() < deadline
+                {
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    match client.get_task(&response.task_id, &claimed.discord_id).await {
+                        Ok(polled) => response = polled,
+                        Err(e) => {
+                            error!("Failed to poll dispatched job {}: {}", claimed.job_id, e);
+                            break;
+                        }
+                    }
+                }
+                match response.status {
+                    TaskStatus::Completed => JobState::Succeeded,
+                    _ => JobState::Failed,
+                }
+            }
+            Err(e) => {
+                error!("Failed to submit job {} to target '{}': {}", claimed.job_id, target, e);
+                JobState::Failed
+            }
+        };
+
+        if let Err(e) = jobs.mark_terminal(&claimed.job_id, final_state).await {
+            error!("Failed to mark job {} {}: {}", claimed.job_id, final_state, e);
+        }
+
+        notifier
+            .notify(&JobRecord { target: Some(target), state: final_state, ..claimed })
+            .await;
+    }
+}
@@ -0,0 +1,272 @@
+//! Durable SQLite-backed job queue for tasks dispatched to a pool of
+//! runnable targets (registered local wrappers and cluster nodes).
+//!
+//! Distinct from `db::TaskStore`: that table mirrors the status of a task
+//! the wrapper service is *already* running. This one exists *before* a
+//! target has even been chosen - `dispatch::run` pulls `Queued` rows, picks
+//! a target, and drives them through `Running` to a terminal state, so a
+//! bot restart doesn't lose track of work that hadn't been handed off yet.
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use uuid::Uuid;
+
+/// A job's position in its dispatch lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobState {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobState {
+    fn as_str(self) -> &'static str {
+        match self {
+            JobState::Queued => "queued",
+            JobState::Running => "running",
+            JobState::Succeeded => "succeeded",
+            JobState::Failed => "failed",
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "queued" => Ok(JobState::Queued),
+            "running" => Ok(JobState::Running),
+            "succeeded" => Ok(JobState::Succeeded),
+            "failed" => Ok(JobState::Failed),
+            other => anyhow::bail!("Unknown job state '{}'", other),
+        }
+    }
+}
+
+impl std::fmt::Display for JobState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobState::Queued => write!(f, "Queued"),
+            JobState::Running => write!(f, "Running"),
+            JobState::Succeeded => write!(f, "Succeeded"),
+            JobState::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+/// A newly submitted job, before a target has been chosen.
+#[derive(Debug, Clone)]
+pub struct NewJobRequest {
+    pub discord_id: String,
+    pub channel_id: u64,
+    pub prompt: String,
+    pub project: Option<String>,
+}
+
+/// A row from the `jobs` table.
+#[derive(Debug, Clone)]
+pub struct JobRecord {
+    pub job_id: String,
+    pub discord_id: String,
+    pub channel_id: u64,
+    pub prompt: String,
+    pub project: Option<String>,
+    /// The wrapper URL or cluster node id this job was (or will be) run
+    /// against. `None` until `set_target` records one after
+    /// `claim_next_queued` has claimed the job.
+    pub target: Option<String>,
+    pub state: JobState,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl JobRecord {
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        let state: String = row.get("state")?;
+        let channel_id: i64 = row.get("channel_id")?;
+        Ok(Self {
+            job_id: row.get("job_id")?,
+            discord_id: row.get("discord_id")?,
+            channel_id: channel_id as u64,
+            prompt: row.get("prompt")?,
+            project: row.get("project")?,
+            target: row.get("target")?,
+            state: JobState::parse(&state).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::<dyn std::error::Error + Send + Sync>::from(e.to_string()),
+                )
+            })?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+        })
+    }
+}
+
+/// SQLite-backed job store. `rusqlite::Connection` isn't `Send`-friendly to
+/// share across `.await` points, so every query runs inside
+/// `spawn_blocking` against a connection guarded by a plain `Mutex` - a
+/// single local SQLite file doesn't benefit from the pooling `TaskStore`
+/// uses for its networked Postgres connection.
+#[derive(Clone)]
+pub struct JobStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl JobStore {
+    /// Open (or create) the SQLite database at `path` and ensure the `jobs`
+    /// table exists.
+    pub async fn connect(path: &str) -> Result<Self> {
+        let path = path.to_string();
+        let conn = tokio::task::spawn_blocking(move || -> Result<Connection> {
+            let conn = Connection::open(&path).with_context(|| format!("Failed to open jobs database '{}'", path))?;
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS jobs (
+                    job_id TEXT PRIMARY KEY,
+                    discord_id TEXT NOT NULL,
+                    channel_id INTEGER NOT NULL,
+                    prompt TEXT NOT NULL,
+                    project TEXT,
+                    target TEXT,
+                    state TEXT NOT NULL,
+                    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+                    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+                )",
+            )
+            .context("Failed to create jobs table")?;
+            Ok(conn)
+        })
+        .await
+        .context("jobs database setup task panicked")??;
+
+        Ok(Self { conn: Arc::new(Mutex::new(conn)) })
+    }
+
+    /// Run `f` against the connection on a blocking thread, so a slow disk
+    /// write never stalls the async runtime.
+    async fn with_conn<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || f(&conn.lock().unwrap()))
+            .await
+            .context("jobs database task panicked")?
+    }
+
+    /// Enqueue a new job in the `Queued` state and return its generated id.
+    pub async fn submit_job(&self, request: NewJobRequest) -> Result<String> {
+        let job_id = Uuid::new_v4().to_string();
+        let job_id_for_insert = job_id.clone();
+
+        self.with_conn(move |conn| {
+            conn.execute(
+                "INSERT INTO jobs (job_id, discord_id, channel_id, prompt, project, state)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    job_id_for_insert,
+                    request.discord_id,
+                    request.channel_id as i64,
+                    request.prompt,
+                    request.project,
+                    JobState::Queued.as_str(),
+                ],
+            )
+            .context("Failed to insert job")?;
+            Ok(())
+        })
+        .await?;
+
+        Ok(job_id)
+    }
+
+    /// Look up a single job by id.
+    pub async fn job_status(&self, job_id: &str) -> Result<Option<JobRecord>> {
+        let job_id = job_id.to_string();
+        self.with_conn(move |conn| {
+            conn.query_row("SELECT * FROM jobs WHERE job_id = ?1", params![job_id], JobRecord::from_row)
+                .optional()
+                .context("Failed to query job")
+        })
+        .await
+    }
+
+    /// The `limit` most recent jobs submitted by `discord_id`, newest first.
+    pub async fn recent_for_user(&self, discord_id: &str, limit: i64) -> Result<Vec<JobRecord>> {
+        let discord_id = discord_id.to_string();
+        self.with_conn(move |conn| {
+            let mut stmt = conn
+                .prepare("SELECT * FROM jobs WHERE discord_id = ?1 ORDER BY created_at DESC LIMIT ?2")
+                .context("Failed to prepare recent jobs query")?;
+            let rows = stmt
+                .query_map(params![discord_id, limit], JobRecord::from_row)
+                .context("Failed to query recent jobs")?;
+            rows.collect::<rusqlite::Result<Vec<_>>>().context("Failed to read recent jobs")
+        })
+        .await
+    }
+
+    /// Atomically claim the oldest `Queued` job, moving it to `Running`
+    /// with no target yet - the caller resolves one afterward and persists
+    /// it with `set_target`. Returns `None` if no job is queued.
+    pub async fn claim_next_queued(&self) -> Result<Option<JobRecord>> {
+        self.with_conn(move |conn| {
+            let job_id: Option<String> = conn
+                .query_row(
+                    "SELECT job_id FROM jobs WHERE state = ?1 ORDER BY created_at ASC LIMIT 1",
+                    params![JobState::Queued.as_str()],
+                    |row| row.get(0),
+                )
+                .optional()
+                .context("Failed to find next queued job")?;
+
+            let Some(job_id) = job_id else {
+                return Ok(None);
+            };
+
+            conn.execute(
+                "UPDATE jobs SET state = ?1, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE job_id = ?2",
+                params![JobState::Running.as_str(), job_id],
+            )
+            .context("Failed to claim job")?;
+
+            conn.query_row("SELECT * FROM jobs WHERE job_id = ?1", params![job_id], JobRecord::from_row)
+                .optional()
+                .context("Failed to reload claimed job")
+        })
+        .await
+    }
+
+    /// Record the wrapper URL or cluster node id a claimed job was
+    /// dispatched to, once `dispatch::run` has resolved one.
+    pub async fn set_target(&self, job_id: &str, target: &str) -> Result<()> {
+        let job_id = job_id.to_string();
+        let target = target.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "UPDATE jobs SET target = ?1, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE job_id = ?2",
+                params![target, job_id],
+            )
+            .context("Failed to set job target")?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Move a `Running` job to a terminal state (`Succeeded` or `Failed`).
+    pub async fn mark_terminal(&self, job_id: &str, state: JobState) -> Result<()> {
+        debug_assert!(matches!(state, JobState::Succeeded | JobState::Failed));
+        let job_id = job_id.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "UPDATE jobs SET state = ?1, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE job_id = ?2",
+                params![state.as_str(), job_id],
+            )
+            .context("Failed to mark job terminal")?;
+            Ok(())
+        })
+        .await
+    }
+}
@@ -1,47 +1,109 @@
 //! Discord bot for orchestrating Claude Code instances.
 
 mod client;
+mod cluster;
 mod commands;
 mod config;
+mod db;
+mod dispatch;
+mod jobs;
+mod notify;
 
-use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context as _, Result};
 use serenity::all::{
     Client, Context, EventHandler, GatewayIntents, Interaction, Ready,
 };
 use serenity::async_trait;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
-use client::WrapperClient;
+use client::{ClusterClient, ClusterNode, WrapperClient};
+use cluster::{ClusterMetadata, NodeRegistry};
+use commands::CommandManager;
 use config::Config;
+use db::TaskStore;
+use jobs::JobStore;
 
 /// Bot event handler.
 struct Handler {
     wrapper: WrapperClient,
-    guild_id: Option<u64>,
+    commands: CommandManager,
+    config: Config,
+    task_store: TaskStore,
+    job_store: JobStore,
+    node_registry: NodeRegistry,
+    cluster_client: ClusterClient,
+    /// Guards against spawning a second task-events consumer if Discord
+    /// fires `ready` again after a reconnect.
+    notify_consumer_started: AtomicBool,
+    /// Guards against spawning a second job dispatcher for the same reason.
+    dispatcher_started: AtomicBool,
 }
 
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
         info!("Bot connected as {}", ready.user.name);
-        commands::register_commands(&ctx, &ready, self.guild_id).await;
+        self.commands
+            .register_with_discord(&ctx, &ready, self.config.guild_id)
+            .await;
+
+        if !self.notify_consumer_started.swap(true, Ordering::SeqCst) {
+            tokio::spawn(notify::run(ctx.clone(), self.wrapper.clone(), self.task_store.clone()));
+        }
+
+        if !self.dispatcher_started.swap(true, Ordering::SeqCst) {
+            tokio::spawn(dispatch::run(
+                self.wrapper.clone(),
+                self.job_store.clone(),
+                self.node_registry.clone(),
+                dispatch::DiscordJobNotifier::new(ctx),
+            ));
+        }
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::Command(command) = interaction {
-            info!("Received command: {}", command.data.name);
-
-            match command.data.name.as_str() {
-                "task" => commands::task(&ctx, &command, &self.wrapper).await,
-                "status" => commands::status(&ctx, &command, &self.wrapper).await,
-                "approve" => commands::approve(&ctx, &command, &self.wrapper).await,
-                "project" => commands::project(&ctx, &command, &self.wrapper).await,
-                "register" => commands::handle_register(&ctx, &command, &self.wrapper).await,
-                "share" => commands::share(&ctx, &command, &self.wrapper).await,
-                _ => {
+        match interaction {
+            Interaction::Command(command) => {
+                info!("Received command: {}", command.data.name);
+
+                if !self
+                    .commands
+                    .dispatch(
+                        &ctx,
+                        &command,
+                        &self.wrapper,
+                        &self.config,
+                        &self.task_store,
+                        &self.job_store,
+                        &self.cluster_client,
+                    )
+                    .await
+                {
                     error!("Unknown command: {}", command.data.name);
                 }
             }
+            Interaction::Component(component) => {
+                if component.data.custom_id.starts_with("approve:") {
+                    commands::handle_component(&ctx, &component, &self.wrapper, &self.task_store).await;
+                } else {
+                    warn!("Unknown component interaction: {}", component.data.custom_id);
+                }
+            }
+            Interaction::Autocomplete(autocomplete) => {
+                if matches!(autocomplete.data.name.as_str(), "project" | "task") {
+                    commands::project_autocomplete(&ctx, &autocomplete, &self.wrapper).await;
+                }
+            }
+            Interaction::Modal(modal) => {
+                if modal.data.custom_id.starts_with("approve_modal:") {
+                    commands::handle_modal_submit(&ctx, &modal, &self.wrapper, &self.task_store).await;
+                } else {
+                    warn!("Unknown modal interaction: {}", modal.data.custom_id);
+                }
+            }
+            _ => {}
         }
     }
 }
@@ -68,20 +130,86 @@ async fn main() -> Result<()> {
         Err(e) => error!("Wrapper service not available: {} (bot will retry on commands)", e),
     }
 
+    // Keep a handle to the wrapper client around for the shutdown path,
+    // since `wrapper` itself is moved into the event handler below.
+    let shutdown_wrapper = wrapper.clone();
+
+    // `config` is moved into the event handler below, so grab the token
+    // first.
+    let discord_token = config.discord_token.clone();
+
+    let task_store = TaskStore::connect(&config.database_url)
+        .await
+        .context("Failed to connect to task registry database")?;
+
+    let job_store = JobStore::connect(&config.jobs_database_path)
+        .await
+        .context("Failed to open jobs database")?;
+
+    // An absent metadata file just means no cluster nodes are dispatchable
+    // yet - jobs fall back to the submitting user's local wrapper.
+    let cluster_metadata = match &config.cluster_metadata_path {
+        Some(path) => ClusterMetadata::load(path).context("Failed to load cluster metadata")?,
+        None => ClusterMetadata::empty(),
+    };
+    let node_registry = NodeRegistry::new(cluster_metadata);
+
+    // `ClusterClient` talks to each node's own `base_url` directly (for
+    // `/cluster status`'s concurrent health fan-out), independent of the
+    // reachability `node_registry` tracks for dispatch.
+    let cluster_nodes = node_registry
+        .metadata()
+        .nodes()
+        .iter()
+        .map(|n| ClusterNode {
+            node_id: n.node_id.clone(),
+            base_url: n.address.clone(),
+        })
+        .collect();
+    let cluster_client = ClusterClient::new(cluster_nodes, None);
+
     // Create Discord client
+    let commands = CommandManager::new(&config);
     let handler = Handler {
         wrapper,
-        guild_id: config.guild_id,
+        commands,
+        config,
+        task_store,
+        job_store,
+        node_registry,
+        cluster_client,
+        notify_consumer_started: AtomicBool::new(false),
+        dispatcher_started: AtomicBool::new(false),
     };
 
     let intents = GatewayIntents::empty();
-    let mut client = Client::builder(&config.discord_token, intents)
+    let mut client = Client::builder(&discord_token, intents)
         .event_handler(handler)
         .await?;
 
+    let shard_manager = client.shard_manager.clone();
+
     // Run the bot
     info!("Connecting to Discord...");
-    client.start().await?;
+    tokio::select! {
+        result = client.start() => result?,
+        _ = tokio::signal::ctrl_c() => {
+            info!("Shutdown signal received, attempting to cancel in-flight tasks...");
+            graceful_shutdown(&shutdown_wrapper).await;
+            shard_manager.shutdown_all().await;
+        }
+    }
 
     Ok(())
 }
+
+/// Best-effort cancellation of every task this process believes is still
+/// running, so a restart doesn't leave orphaned wrapper jobs running.
+async fn graceful_shutdown(wrapper: &WrapperClient) {
+    for (task_id, user_id) in commands::inflight_tasks() {
+        match wrapper.cancel_task(&task_id, &user_id).await {
+            Ok(response) => info!("Cancelled task {} on shutdown: {}", task_id, response.status),
+            Err(e) => warn!("Failed to cancel task {} on shutdown: {}", task_id, e),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Background consumer that turns task-lifecycle events from the wrapper's
+//! durable "task-events" queue into Discord completion notices, so tasks
+//! that outlive the original `/task` interaction still get reported instead
+//! of requiring the user to poll `/status`.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serenity::all::{ChannelId, Context, CreateMessage, UserId};
+use tracing::{error, warn};
+
+use crate::client::{TaskEventMessage, TaskStatus, WrapperClient};
+use crate::db::TaskStore;
+
+/// How many events to request per long-poll round-trip.
+const BATCH_SIZE: u32 = 20;
+
+/// How long each long-poll waits for new events before returning empty.
+const LONG_POLL_WAIT: Duration = Duration::from_secs(20);
+
+/// Backoff after a failed poll, doubling each consecutive failure up to
+/// `MAX_BACKOFF`, then reset once a poll succeeds again.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Where to deliver a completion notice for a task: the channel `/task run`
+/// was invoked in, and the Discord user to mention.
+#[derive(Debug, Clone)]
+struct NotifyTarget {
+    channel_id: ChannelId,
+    user_id: UserId,
+}
+
+/// Notify targets keyed by task ID, registered by the command handler right
+/// after submission and consumed (looked up and removed) here once a
+/// terminal event arrives. Best-effort, in-memory only — a bot restart
+/// loses pending registrations, same tradeoff as `commands::task`'s
+/// in-flight tracking.
+static TARGETS: OnceLock<Mutex<HashMap<String, NotifyTarget>>> = OnceLock::new();
+
+fn targets() -> &'static Mutex<HashMap<String, NotifyTarget>> {
+    TARGETS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record where to deliver a completion notice for `task_id`. Called right
+/// after a successful `submit_task` for a non-terminal status.
+pub fn register_target(task_id: &str, channel_id: ChannelId, user_id: UserId) {
+    targets().lock().unwrap().insert(task_id.to_string(), NotifyTarget { channel_id, user_id });
+}
+
+/// Run the consumer loop for the life of the process: long-poll the
+/// wrapper's task-events queue, post a completion notice for each terminal
+/// event, ack it, and reconnect with exponential backoff after a failed
+/// poll. Intended to be spawned once as a background task.
+pub async fn run(ctx: Context, wrapper: WrapperClient, task_store: TaskStore) {
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        match wrapper.poll_task_events(BATCH_SIZE, LONG_POLL_WAIT).await {
+            Ok(events) => {
+                backoff = INITIAL_BACKOFF;
+                for event in events {
+                    handle_event(&ctx, &wrapper, &task_store, &event).await;
+                }
+            }
+            Err(e) => {
+                warn!("Task-event poll failed, retrying in {:?}: {}", backoff, e);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+}
+
+/// Post a notice for one completed, failed, or needs-approval event - to its
+/// known channel, or by DM to the submitting user when no channel is known -
+/// and ack it. `Pending`/`Running` events only update the stored status
+/// before being acked, so they don't get redelivered forever either.
+async fn handle_event(ctx: &Context, wrapper: &WrapperClient, task_store: &TaskStore, event: &TaskEventMessage) {
+    if matches!(event.status, TaskStatus::Pending | TaskStatus::Running) {
+        if let Err(e) = task_store.update_status(&event.task_id, &event.status).await {
+            error!("Failed to persist status for task {}: {}", event.task_id, e);
+        }
+        ack(wrapper, &event.event_id).await;
+        return;
+    }
+
+    let status_emoji = match event.status {
+        TaskStatus::Completed => "✅",
+        TaskStatus::Failed => "❌",
+        TaskStatus::NeedsApproval => "⚠️",
+        TaskStatus::Pending | TaskStatus::Running => unreachable!("handled above"),
+    };
+    let detail = format!(
+        "Task `{}` is now **{}**. Run `/status task_id:{}` for details.",
+        event.task_id, event.status, event.task_id,
+    );
+
+    let target = match targets().lock().unwrap().get(&event.task_id).cloned() {
+        Some(target) => Some((target.channel_id, Some(target.user_id))),
+        // No in-memory target (bot restart, or the event simply outlived
+        // the process that submitted it) — fall back to the persistent
+        // record before giving up and DMing the submitting user directly.
+        None => match task_store.notify_target(&event.task_id).await {
+            Ok(Some((channel_id, user_id))) => {
+                Some((ChannelId::new(channel_id), user_id.parse::<u64>().ok().map(UserId::new)))
+            }
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Failed to look up persisted target for task {}: {}", event.task_id, e);
+                None
+            }
+        },
+    };
+
+    match target {
+        Some((channel_id, user_id)) => {
+            let content = match user_id {
+                Some(user_id) => format!("{} <@{}> {}", status_emoji, user_id, detail),
+                None => format!("{} {}", status_emoji, detail),
+            };
+            if let Err(e) = channel_id.send_message(&ctx.http, CreateMessage::new().content(content)).await {
+                error!("Failed to post task completion notice for {}: {}", event.task_id, e);
+            }
+        }
+        None => send_dm_fallback(ctx, event, &format!("{} {}", status_emoji, detail)).await,
+    }
+
+    if matches!(event.status, TaskStatus::Completed | TaskStatus::Failed) {
+        targets().lock().unwrap().remove(&event.task_id);
+    }
+
+    if let Err(e) = task_store.update_status(&event.task_id, &event.status).await {
+        error!("Failed to persist status for task {}: {}", event.task_id, e);
+    }
+
+    ack(wrapper, &event.event_id).await;
+}
+
+/// DM the submitting user directly when no channel target is registered for
+/// a task, e.g. because the bot restarted before it finished.
+async fn send_dm_fallback(ctx: &Context, event: &TaskEventMessage, content: &str) {
+    let Some(user_id) = event.discord_user_id.parse::<u64>().ok().map(UserId::new) else {
+        warn!("Task {} has an unparseable discord_user_id, dropping notice", event.task_id);
+        return;
+    };
+
+    let dm_channel = match user_id.create_dm_channel(&ctx.http).await {
+        Ok(channel) => channel,
+        Err(e) => {
+            error!("Failed to open DM for task {} completion notice: {}", event.task_id, e);
+            return;
+        }
+    };
+
+    if let Err(e) = dm_channel.send_message(&ctx.http, CreateMessage::new().content(content)).await {
+        error!("Failed to DM task {} completion notice: {}", event.task_id, e);
+    }
+}
+
+async fn ack(wrapper: &WrapperClient, event_id: &str) {
+    if let Err(e) = wrapper.ack_task_event(event_id).await {
+        error!("Failed to ack task event {}: {}", event_id, e);
+    }
+}